@@ -0,0 +1,132 @@
+//! Optional allowlist mode for KYC-gated token holders.
+//!
+//! While the mode is switched off, which it is after deployment, the list is
+//! ignored. Once switched on, tokens only move between allowlisted accounts:
+//! both parties of a transfer, the recipient of a mint and both the employer
+//! and the worker of a released escrow must be on the list. Accounts holding
+//! the `verifier` role manage the list and the toggle.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// Storage keys for the allowlist.
+#[contracttype]
+pub enum AllowlistStorageKey {
+    /// Set while the allowlist is enforced.
+    Enabled,
+    /// Present for every allowlisted account.
+    Allowed(Address),
+}
+
+/// Returns true if the allowlist is enforced.
+pub fn is_enabled(e: &Env) -> bool {
+    e.storage().instance().get(&AllowlistStorageKey::Enabled).unwrap_or(false)
+}
+
+/// Switches enforcement of the allowlist on or off.
+///
+/// # Events
+///
+/// * topics - `["allowlist_toggled"]`
+/// * data - `[enabled: bool]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn set_enabled(e: &Env, enabled: bool) {
+    e.storage().instance().set(&AllowlistStorageKey::Enabled, &enabled);
+
+    AllowlistToggled { enabled }.publish(e);
+}
+
+/// Returns true if `account` is on the allowlist, whether or not it is
+/// enforced.
+pub fn is_allowed(e: &Env, account: &Address) -> bool {
+    let key = AllowlistStorageKey::Allowed(account.clone());
+    let allowed = e.storage().persistent().has(&key);
+    if allowed {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    allowed
+}
+
+/// Panics if the allowlist is enforced and `account` is not on it.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::NotAllowlisted`] - When the allowlist is
+///   enforced and `account` is not on it.
+pub fn ensure_allowed(e: &Env, account: &Address) {
+    if is_enabled(e) && !is_allowed(e, account) {
+        panic_with_error!(e, ExampleContractError::NotAllowlisted);
+    }
+}
+
+/// Adds `accounts` to the allowlist.
+///
+/// # Events
+///
+/// For each account:
+/// * topics - `["account_allowed", account: Address]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn allow(e: &Env, accounts: &Vec<Address>) {
+    for account in accounts.iter() {
+        let key = AllowlistStorageKey::Allowed(account.clone());
+        e.storage().persistent().set(&key, &true);
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+        AccountAllowed { account }.publish(e);
+    }
+}
+
+/// Removes `accounts` from the allowlist.
+///
+/// # Events
+///
+/// For each account:
+/// * topics - `["account_disallowed", account: Address]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn disallow(e: &Env, accounts: &Vec<Address>) {
+    for account in accounts.iter() {
+        e.storage().persistent().remove(&AllowlistStorageKey::Allowed(account.clone()));
+
+        AccountDisallowed { account }.publish(e);
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when enforcement of the allowlist is switched.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistToggled {
+    pub enabled: bool,
+}
+
+/// Event emitted when an account is added to the allowlist.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountAllowed {
+    #[topic]
+    pub account: Address,
+}
+
+/// Event emitted when an account is removed from the allowlist.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountDisallowed {
+    #[topic]
+    pub account: Address,
+}
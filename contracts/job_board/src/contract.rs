@@ -2,21 +2,82 @@
 
 //! This contract showcases how to integrate various OpenZeppelin modules to
 //! build a fully SEP-41-compliant fungible token. It includes essential
-//! features such as an emergency stop mechanism and controlled token minting by
-//! the owner.
+//! features such as an emergency stop mechanism, which can also stop single
+//! categories of operations, and role-gated token minting, bounded by a
+//! maximum supply and an optional per-epoch mint budget.
+//!
+//! Privileged operations are gated by roles from the access control module:
+//! `minter`, `pauser`, `arbiter` (escrow releases and dispute rulings),
+//! `upgrader`, `freezer` (per-account freezes) and `verifier` (the optional
+//! allowlist of KYC-verified holders), all managed by the access control
+//! admin, referred to as the owner. The deployer becomes the owner and starts
+//! out holding every role. Ownership changes hands in two steps: the owner
+//! proposes a new owner, who has until a given ledger to accept.
+//!
+//! The owner can also set up an M-of-N signer set. From then on, minting,
+//! vesting grants, releases, upgrades, migrations and pausing are proposed by
+//! a signer and carried out once enough signers approved the proposal, and
+//! their single-key entry points are disabled.
+//!
+//! Besides one-shot escrows, employers can pay retainers through streams that
+//! accrue to the worker at a fixed rate per ledger. Grants can be minted into
+//! vesting schedules, which their beneficiaries claim from as the tokens vest.
+//! Released escrows and dispute rulings feed a per-account reputation record
+//! that anyone can query, and the parties of a released job can rate each
+//! other.
+//!
+//! Holders can approve a spender through an off-chain signed permit, which
+//! anyone can submit on their behalf.
 //!
 //! To meet SEP-41 compliance, the contract must implement both
 //! [`stellar_fungible::fungible::FungibleToken`] and
 //! [`stellar_fungible::burnable::FungibleBurnable`].
 
+use core::cmp::Ordering;
+
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol, contract, contracterror, contractimpl, panic_with_error, symbol_short
+    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, panic_with_error, symbol_short
 };
+use stellar_access::access_control::{self as access_control, AccessControl};
 use stellar_contract_utils::pausable::{self as pausable, Pausable};
-use stellar_macros::when_not_paused;
-use stellar_tokens::fungible::{burnable::FungibleBurnable, Base, FungibleToken};
+use stellar_macros::{only_admin, only_role, when_not_paused};
+use stellar_tokens::fungible::{
+    burnable::{emit_burn, FungibleBurnable},
+    emit_transfer, Base, FungibleToken,
+};
+
+use crate::{
+    allowlist,
+    dispute::{self, Dispute, BPS_DENOMINATOR},
+    escrow::{self, Escrow, EscrowRefunded, EscrowReleased, MilestoneReleased},
+    fee::{self, FeeConfig, FeeTaken, StreamFeeTaken},
+    freeze,
+    migration,
+    multisig::{self, Action, Proposal},
+    pausing::{self, PauseCategory},
+    permit,
+    rating::{self, Rating, RatingSummary},
+    reputation::{self, Reputation},
+    stream::{self, Stream},
+    supply::{self, MintBudget},
+    upgrade::{self, PendingUpgrade},
+    vesting::{self, VestingSchedule},
+};
+
+pub const MINTER_ROLE: Symbol = symbol_short!("minter");
+pub const PAUSER_ROLE: Symbol = symbol_short!("pauser");
+pub const ARBITER_ROLE: Symbol = symbol_short!("arbiter");
+pub const UPGRADER_ROLE: Symbol = symbol_short!("upgrader");
+pub const FREEZER_ROLE: Symbol = symbol_short!("freezer");
+pub const VERIFIER_ROLE: Symbol = symbol_short!("verifier");
 
-pub const OWNER: Symbol = symbol_short!("OWNER");
+/// Maximum number of entries in a `batch_transfer` or `batch_release` call.
+///
+/// Each released escrow writes its record, the worker balance, the worker
+/// reputation and, for a delivered job, removes its delivery record, so a full
+/// batch of releases stays within the 50 ledger entries a transaction may
+/// write under the current network settings.
+pub const MAX_BATCH_SIZE: u32 = 11;
 
 #[contract]
 pub struct ExampleContract;
@@ -26,51 +87,873 @@ pub struct ExampleContract;
 #[repr(u32)]
 pub enum ExampleContractError {
     Unauthorized = 1,
+    EscrowNotFound = 2,
+    EscrowAlreadyExists = 3,
+    EscrowNotLocked = 4,
+    InvalidAmount = 5,
+    MilestoneNotFound = 6,
+    InvalidMilestoneState = 7,
+    EscrowNotDisputed = 8,
+    InvalidSplit = 9,
+    InvalidDeadline = 10,
+    DeliveryWindowClosed = 11,
+    RefundNotAvailable = 12,
+    DeliveryPending = 13,
+    WorkerNotAssigned = 14,
+    WorkerAlreadyAssigned = 15,
+    FeeTooHigh = 16,
+    UpgradeNotScheduled = 17,
+    UpgradeAlreadyScheduled = 18,
+    UpgradeNotReady = 19,
+    AlreadyMigrated = 20,
+    NotSigner = 21,
+    InvalidSigners = 22,
+    ProposalNotFound = 23,
+    ProposalExpired = 24,
+    ProposalAlreadyExecuted = 25,
+    AlreadyApproved = 26,
+    ThresholdNotReached = 27,
+    SupplyCapExceeded = 28,
+    MintBudgetExceeded = 29,
+    AccountFrozen = 30,
+    NotAllowlisted = 31,
+    OperationPaused = 32,
+    BatchTooLarge = 33,
+    InvalidNonce = 34,
+    PermitExpired = 35,
+    VestingNotFound = 36,
+    InvalidSchedule = 37,
+    VestingRevoked = 38,
+    StreamNotFound = 39,
+    StreamNotActive = 40,
+    StreamNotPaused = 41,
+    EscrowNotReleased = 42,
+    InvalidScore = 43,
+    AlreadyRated = 44,
+    MigrationNotFound = 45,
+    JobIdReserved = 46,
+    EmployerIsWorker = 47,
+    HiringWindowClosed = 48,
+    MultisigRequired = 49,
 }
 
 #[contractimpl]
 impl ExampleContract {
-    pub fn __constructor(e: &Env, owner: Address, initial_supply: i128) {
+    pub fn __constructor(e: &Env, owner: Address, initial_supply: i128, max_supply: i128) {
         Base::set_metadata(e, 18, String::from_str(e, "JobBoardToken"), String::from_str(e, "JBT"));
+        supply::set_max_supply(e, max_supply);
+        supply::record_mint(e, initial_supply);
         Base::mint(e, &owner, initial_supply);
-        e.storage().instance().set(&OWNER, &owner);
+
+        access_control::set_admin(e, &owner);
+        for role in [MINTER_ROLE, PAUSER_ROLE, ARBITER_ROLE, UPGRADER_ROLE, FREEZER_ROLE, VERIFIER_ROLE] {
+            access_control::grant_role_no_auth(e, &owner, &owner, &role);
+        }
+        migration::set_initial_version(e);
     }
 
     #[when_not_paused]
-    pub fn mint(e: &Env, account: Address, amount: i128) {
-        let owner: Address = e.storage().instance().get(&OWNER).expect("owner should be set");
-        owner.require_auth();
-
+    #[only_role(caller, "minter")]
+    pub fn mint(e: &Env, account: Address, amount: i128, caller: Address) {
+        multisig::ensure_single_key(e);
+        pausing::when_not_paused(e, PauseCategory::Mint);
+        freeze::ensure_not_frozen(e, &account);
+        allowlist::ensure_allowed(e, &account);
+        supply::record_mint(e, amount);
         Base::mint(e, &account, amount);
     }
 
+    pub fn max_supply(e: &Env) -> i128 {
+        supply::max_supply(e)
+    }
+
+    #[only_admin]
+    pub fn set_mint_budget(e: &Env, budget: Option<MintBudget>) {
+        supply::set_mint_budget(e, &budget);
+    }
+
+    pub fn mint_budget(e: &Env) -> Option<MintBudget> {
+        supply::mint_budget(e)
+    }
+
+    pub fn remaining_mint_budget(e: &Env) -> Option<i128> {
+        supply::remaining_mint_budget(e)
+    }
+
     #[when_not_paused]
-    pub fn lock(e: &Env, from: Address, amount: i128) {
-        // Escrow model: move tokens from the user into the contract's balance.
-        // This prevents the user from spending them while locked.
-        // Security: user must authorize moving their own funds.
-        // from.require_auth();
+    #[only_admin]
+    pub fn create_vesting(
+        e: &Env,
+        beneficiary: Address,
+        total: i128,
+        start_ledger: u32,
+        cliff_ledger: u32,
+        end_ledger: u32,
+    ) -> u32 {
+        multisig::ensure_single_key(e);
+        pausing::when_not_paused(e, PauseCategory::Mint);
+        Self::mint_vesting(e, &beneficiary, total, start_ledger, cliff_ledger, end_ledger)
+    }
 
-        let contract_addr: Address = e.current_contract_address();
-        Base::transfer(e, &from, &contract_addr, amount);
+    #[when_not_paused]
+    pub fn claim_vesting(e: &Env, id: u32) -> i128 {
+        pausing::when_not_paused(e, PauseCategory::Transfers);
+        // Only the beneficiary can claim, and is subject to the same checks
+        // as the recipient of a transfer.
+        let schedule = vesting::read_schedule(e, id);
+        schedule.beneficiary.require_auth();
+        freeze::ensure_not_frozen(e, &schedule.beneficiary);
+        allowlist::ensure_allowed(e, &schedule.beneficiary);
+
+        let amount = vesting::claim(e, id);
+        if amount > 0 {
+            Self::pay_out(e, &schedule.beneficiary, amount);
+        }
+        amount
+    }
+
+    #[when_not_paused]
+    #[only_admin]
+    pub fn revoke_vesting(e: &Env, id: u32) -> i128 {
+        pausing::when_not_paused(e, PauseCategory::Burn);
+        // The unvested part was minted for the grant, so it is burned rather
+        // than handed to the owner.
+        let unvested = vesting::revoke(e, id);
+        let contract_addr = e.current_contract_address();
+        Base::update(e, Some(&contract_addr), None, unvested);
+        emit_burn(e, &contract_addr, unvested);
+        unvested
+    }
+
+    pub fn get_vesting(e: &Env, id: u32) -> VestingSchedule {
+        vesting::read_schedule(e, id)
+    }
+
+    pub fn vested_amount(e: &Env, id: u32) -> i128 {
+        vesting::vested_amount(e, &vesting::read_schedule(e, id))
+    }
+
+    pub fn vesting_count(e: &Env) -> u32 {
+        vesting::schedule_count(e)
+    }
+
+    #[when_not_paused]
+    pub fn batch_transfer(e: &Env, from: Address, transfers: Vec<(Address, i128)>) {
+        pausing::when_not_paused(e, PauseCategory::Transfers);
+        // A single authorization from `from` covers the whole batch, which
+        // goes through entirely or not at all. Each entry is subject to the
+        // same freeze and allowlist checks as `transfer`.
+        from.require_auth();
+        Self::ensure_batch_size(e, transfers.len());
+        freeze::ensure_not_frozen(e, &from);
+        allowlist::ensure_allowed(e, &from);
+        for (to, amount) in transfers.iter() {
+            freeze::ensure_not_frozen(e, &to);
+            allowlist::ensure_allowed(e, &to);
+            Base::update(e, Some(&from), Some(&to), amount);
+            emit_transfer(e, &from, &to, amount);
+        }
+    }
+
+    pub fn permit(
+        e: &Env,
+        public_key: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u32,
+        signature: BytesN<64>,
+    ) {
+        // No authorization needed: the owner's signature over the permit
+        // stands in for it, so anyone can submit it and pay the fee.
+        permit::permit(e, &public_key, &spender, amount, expiration_ledger, nonce, &signature);
+    }
+
+    pub fn nonce(e: &Env, owner: Address) -> u32 {
+        permit::nonce(e, &owner)
+    }
+
+    #[when_not_paused]
+    pub fn lock(
+        e: &Env,
+        employer: Address,
+        job_id: u64,
+        worker: Address,
+        amount: i128,
+        deadline_ledger: u32,
+    ) {
+        pausing::when_not_paused(e, PauseCategory::EscrowIn);
+        // Escrow model: move tokens from the employer into the contract's
+        // balance. This prevents the employer from spending them while locked.
+        // Security: the employer must authorize moving their own funds, and is
+        // recorded as the depositor of the escrow.
+        employer.require_auth();
+        freeze::ensure_not_frozen(e, &employer);
+
+        escrow::create_escrow(e, job_id, &employer, &worker, amount, deadline_ledger);
+        Self::pay_in(e, &employer, amount);
+    }
+
+    #[when_not_paused]
+    pub fn lock_milestones(
+        e: &Env,
+        employer: Address,
+        job_id: u64,
+        worker: Address,
+        amounts: Vec<i128>,
+        deadline_ledger: u32,
+    ) {
+        pausing::when_not_paused(e, PauseCategory::EscrowIn);
+        // Same as `lock`, but the escrow is split into milestones that are
+        // released one by one through `approve_milestone`.
+        employer.require_auth();
+        freeze::ensure_not_frozen(e, &employer);
+
+        let escrow = escrow::create_milestone_escrow(
+            e,
+            job_id,
+            &employer,
+            &worker,
+            &amounts,
+            deadline_ledger,
+        );
+        Self::pay_in(e, &employer, escrow.amount);
+    }
+
+    #[when_not_paused]
+    pub fn lock_budget(
+        e: &Env,
+        employer: Address,
+        manager: Option<Address>,
+        amount: i128,
+        deadline_ledger: u32,
+    ) -> u64 {
+        pausing::when_not_paused(e, PauseCategory::EscrowIn);
+        // Same as `lock`, for jobs that have not hired a worker yet, and
+        // returns the job id allocated to the escrow. Until a worker is
+        // assigned, the budget can be taken back with `refund` at any time,
+        // by the manager if one is set and by the employer otherwise.
+        employer.require_auth();
+        if let Some(manager) = &manager {
+            manager.require_auth();
+        }
+        freeze::ensure_not_frozen(e, &employer);
+
+        let escrow =
+            escrow::create_budget_escrow(e, &employer, manager.as_ref(), amount, deadline_ledger);
+        Self::pay_in(e, &employer, amount);
+        escrow.job_id
+    }
+
+    #[when_not_paused]
+    pub fn assign_worker(e: &Env, job_id: u64, worker: Address) {
+        // Turns a budget locked with `lock_budget` into a regular escrow for
+        // the hired worker. Budgets locked through a manager can only be
+        // assigned by it.
+        let mut escrow = escrow::read_escrow(e, job_id);
+        escrow::budget_authority(e, &escrow).require_auth();
+
+        escrow::assign_worker(e, &mut escrow, &worker);
+        escrow::write_escrow(e, &escrow);
+    }
+
+    #[when_not_paused]
+    #[only_role(operator, "arbiter")]
+    pub fn release(e: &Env, job_id: u64, operator: Address) {
+        multisig::ensure_single_key(e);
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // Release whatever is still locked for `job_id` to the job's worker.
+        // Only arbiters can release escrowed tokens.
+        Self::release_escrow(e, job_id);
+    }
+
+    #[when_not_paused]
+    #[only_role(operator, "arbiter")]
+    pub fn batch_release(e: &Env, releases: Vec<(u64, i128)>, operator: Address) {
+        multisig::ensure_single_key(e);
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // Releases each `(job_id, amount)` entry like `release`, where
+        // `amount` is what the caller expects to be still locked for the job.
+        // Any failing entry, including an amount mismatch, reverts the batch.
+        Self::ensure_batch_size(e, releases.len());
+        for (job_id, amount) in releases.iter() {
+            if Self::release_escrow(e, job_id) != amount {
+                panic_with_error!(e, ExampleContractError::InvalidAmount);
+            }
+        }
+    }
+
+    #[when_not_paused]
+    pub fn deliver_milestone(e: &Env, job_id: u64, index: u32) {
+        let mut escrow = escrow::read_escrow(e, job_id);
+        escrow::worker(e, &escrow).require_auth();
+
+        escrow::deliver_milestone(e, &mut escrow, index);
+        escrow::write_escrow(e, &escrow);
+        reputation::record_delivery(e, &escrow);
+    }
+
+    #[when_not_paused]
+    pub fn submit_delivery(e: &Env, job_id: u64) {
+        let mut escrow = escrow::read_escrow(e, job_id);
+        escrow::worker(e, &escrow).require_auth();
+
+        escrow::submit_delivery(e, &mut escrow);
+        escrow::write_escrow(e, &escrow);
+        reputation::record_delivery(e, &escrow);
+    }
+
+    #[when_not_paused]
+    pub fn refund(e: &Env, job_id: u64) {
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // Once the deadline and grace period have passed without delivered
+        // work, the employer can take back whatever is still locked. A budget
+        // without a worker is refunded through its manager, if it has one.
+        let mut escrow = escrow::read_escrow(e, job_id);
+        if escrow.worker.is_none() {
+            escrow::budget_authority(e, &escrow).require_auth();
+        } else {
+            escrow.employer.require_auth();
+        }
+
+        let remaining = escrow::refund(e, &mut escrow);
+        escrow::write_escrow(e, &escrow);
+        reputation::clear_delivery(e, &escrow);
+
+        Self::pay_out(e, &escrow.employer, remaining);
+        EscrowRefunded { job_id, employer: escrow.employer, amount: remaining }.publish(e);
+    }
+
+    #[when_not_paused]
+    pub fn create_stream(
+        e: &Env,
+        employer: Address,
+        worker: Address,
+        deposit: i128,
+        rate_per_ledger: i128,
+    ) -> u32 {
+        pausing::when_not_paused(e, PauseCategory::EscrowIn);
+        // Same model as `lock`: the employer authorizes moving the deposit
+        // into the contract's balance, from which the stream is paid.
+        employer.require_auth();
+        freeze::ensure_not_frozen(e, &employer);
+
+        let id = stream::create_stream(e, &employer, &worker, deposit, rate_per_ledger);
+        Self::pay_in(e, &employer, deposit);
+        id
+    }
+
+    #[when_not_paused]
+    pub fn withdraw_stream(e: &Env, id: u32) -> i128 {
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // Only the worker can withdraw, at any time, whatever has accrued.
+        let mut stream = stream::read_stream(e, id);
+        stream.worker.require_auth();
+
+        let amount = stream::withdraw(e, &mut stream);
+        stream::write_stream(e, &stream);
+        Self::pay_stream_worker(e, &stream, amount);
+        amount
+    }
+
+    #[when_not_paused]
+    pub fn pause_stream(e: &Env, id: u32) {
+        let mut stream = stream::read_stream(e, id);
+        stream.employer.require_auth();
+        stream::pause(e, &mut stream);
+        stream::write_stream(e, &stream);
+    }
+
+    #[when_not_paused]
+    pub fn resume_stream(e: &Env, id: u32) {
+        let mut stream = stream::read_stream(e, id);
+        stream.employer.require_auth();
+        stream::resume(e, &mut stream);
+        stream::write_stream(e, &stream);
+    }
+
+    #[when_not_paused]
+    pub fn cancel_stream(e: &Env, id: u32) {
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // The employer can stop a stream at any time: the worker is paid what
+        // accrued until now and the employer gets the rest of the deposit. A
+        // worker that cannot be paid right now, e.g. because they are frozen,
+        // keeps what accrued in the stream and withdraws it later, so the
+        // refund does not depend on them.
+        let mut stream = stream::read_stream(e, id);
+        stream.employer.require_auth();
+
+        let (worker_amount, refund) = stream::cancel(e, &mut stream);
+        let payout = if worker_amount > 0 && Self::stream_worker_payable(e, &stream) {
+            stream::withdraw(e, &mut stream)
+        } else {
+            0
+        };
+        stream::write_stream(e, &stream);
+        Self::pay_stream_worker(e, &stream, payout);
+        if refund > 0 {
+            Self::pay_out(e, &stream.employer, refund);
+        }
+    }
+
+    pub fn get_stream(e: &Env, id: u32) -> Stream {
+        stream::read_stream(e, id)
+    }
+
+    pub fn stream_withdrawable(e: &Env, id: u32) -> i128 {
+        stream::withdrawable(e, &stream::read_stream(e, id))
+    }
+
+    pub fn stream_count(e: &Env) -> u32 {
+        stream::stream_count(e)
+    }
+
+    pub fn category_paused(e: &Env, category: PauseCategory) -> bool {
+        pausing::is_paused(e, category)
+    }
+
+    pub fn paused_categories(e: &Env) -> Vec<PauseCategory> {
+        pausing::paused_categories(e)
+    }
+
+    #[only_role(caller, "pauser")]
+    pub fn pause_category(e: &Env, category: PauseCategory, caller: Address) {
+        multisig::ensure_single_key(e);
+        pausing::pause(e, category);
+    }
+
+    #[only_role(caller, "pauser")]
+    pub fn unpause_category(e: &Env, category: PauseCategory, caller: Address) {
+        multisig::ensure_single_key(e);
+        pausing::unpause(e, category);
+    }
+
+    #[only_admin]
+    pub fn set_grace_period(e: &Env, ledgers: u32) {
+        escrow::set_grace_period(e, ledgers);
+    }
+
+    pub fn grace_period(e: &Env) -> u32 {
+        escrow::grace_period(e)
+    }
+
+    #[when_not_paused]
+    pub fn approve_milestone(e: &Env, caller: Address, job_id: u64, index: u32) {
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // Either the employer or an arbiter can approve a delivered milestone.
+        caller.require_auth();
+        let mut escrow = escrow::read_escrow(e, job_id);
+        if caller != escrow.employer && !dispute::is_arbiter(e, &caller) {
+            panic_with_error!(e, ExampleContractError::Unauthorized);
+        }
+
+        let amount = escrow::release_milestone(e, &mut escrow, index);
+        escrow::write_escrow(e, &escrow);
+
+        let worker = escrow::worker(e, &escrow);
+        let fee = Self::pay_worker(e, &escrow, &worker, amount);
+        MilestoneReleased { job_id, worker, index, amount, fee }.publish(e);
+    }
+
+    #[when_not_paused]
+    pub fn open_dispute(e: &Env, caller: Address, job_id: u64) {
+        // Either party of the job can freeze the escrow until an arbiter rules.
+        caller.require_auth();
+
+        let mut escrow = escrow::read_escrow(e, job_id);
+        dispute::open_dispute(e, &caller, &mut escrow);
+        escrow::write_escrow(e, &escrow);
+    }
+
+    #[when_not_paused]
+    pub fn resolve_dispute(e: &Env, arbiter: Address, job_id: u64, worker_bps: u32) {
+        pausing::when_not_paused(e, PauseCategory::EscrowOut);
+        // An arbiter awards `worker_bps` of the disputed funds to the
+        // worker and refunds the rest to the employer.
+        arbiter.require_auth();
+
+        let mut escrow = escrow::read_escrow(e, job_id);
+        let (worker_amount, employer_amount) =
+            dispute::resolve_dispute(e, &arbiter, &mut escrow, worker_bps);
+        escrow::write_escrow(e, &escrow);
+        reputation::clear_delivery(e, &escrow);
+
+        // The worker's share is subject to the same checks as `pay_worker`.
+        let worker = escrow::worker(e, &escrow);
+        if worker_amount > 0 {
+            freeze::ensure_not_frozen(e, &worker);
+            allowlist::ensure_allowed(e, &escrow.employer);
+            allowlist::ensure_allowed(e, &worker);
+            Self::pay_out(e, &worker, worker_amount);
+            reputation::record_payment(e, &escrow, &worker, worker_amount);
+        }
+        if employer_amount > 0 {
+            Self::pay_out(e, &escrow.employer, employer_amount);
+        }
+
+        // The party awarded less than half of the disputed funds lost the
+        // dispute, an even split counts against neither.
+        match worker_bps.cmp(&(BPS_DENOMINATOR / 2)) {
+            Ordering::Less => reputation::record_dispute_lost(e, &worker),
+            Ordering::Greater => reputation::record_dispute_lost(e, &escrow.employer),
+            Ordering::Equal => {}
+        }
+    }
+
+    pub fn reputation(e: &Env, account: Address) -> Reputation {
+        reputation::reputation(e, &account)
+    }
+
+    pub fn on_time_rate(e: &Env, account: Address) -> Option<u32> {
+        reputation::on_time_rate(e, &account)
     }
 
     #[when_not_paused]
-    pub fn release(e: &Env, to: Address, amount: i128) {
-        // Release locked tokens from the contract to a recipient.
-        // Only the contract owner can release escrowed tokens.
-        let owner: Address = e.storage().instance().get(&OWNER).expect("owner should be set");
-        owner.require_auth();
+    pub fn rate(e: &Env, rater: Address, job_id: u64, score: u32, review_hash: BytesN<32>) {
+        // Each party of a released job rates the other, once. The escrow
+        // record decides who the parties are.
+        rater.require_auth();
+        let escrow = escrow::read_escrow(e, job_id);
+        rating::rate(e, &rater, &escrow, score, &review_hash);
+    }
+
+    pub fn get_rating(e: &Env, job_id: u64, rater: Address) -> Option<Rating> {
+        rating::get_rating(e, job_id, &rater)
+    }
 
+    pub fn rating_summary(e: &Env, account: Address) -> RatingSummary {
+        rating::summary(e, &account)
+    }
+
+    pub fn average_rating(e: &Env, account: Address) -> Option<u32> {
+        rating::average_score(e, &account)
+    }
+
+    pub fn get_dispute(e: &Env, job_id: u64) -> Option<Dispute> {
+        dispute::get_dispute(e, job_id)
+    }
+
+    #[only_admin]
+    pub fn set_fee_config(e: &Env, fee_bps: u32, treasury: Address) {
+        fee::set_fee_config(e, fee_bps, &treasury);
+    }
+
+    pub fn fee_config(e: &Env) -> Option<FeeConfig> {
+        fee::fee_config(e)
+    }
+
+    #[only_admin]
+    pub fn set_signers(e: &Env, signers: Vec<Address>, threshold: u32) {
+        // The owner only bootstraps the multisig, afterwards the signer set
+        // changes through `Action::SetSigners` proposals.
+        if multisig::threshold(e) > 0 {
+            panic_with_error!(e, ExampleContractError::Unauthorized);
+        }
+        multisig::set_signers(e, &signers, threshold);
+    }
+
+    pub fn signers(e: &Env) -> Vec<Address> {
+        multisig::signers(e)
+    }
+
+    pub fn threshold(e: &Env) -> u32 {
+        multisig::threshold(e)
+    }
+
+    pub fn propose(e: &Env, proposer: Address, action: Action, expiration_ledger: u32) -> u32 {
+        // Proposing counts as the proposer's approval.
+        proposer.require_auth();
+        multisig::propose(e, &proposer, &action, expiration_ledger)
+    }
+
+    pub fn approve_proposal(e: &Env, signer: Address, id: u32) {
+        signer.require_auth();
+        multisig::approve(e, &signer, id);
+    }
+
+    pub fn execute_proposal(e: &Env, id: u32) {
+        // No authorization needed: the approvals collected by the proposal
+        // stand in for it. Actions go through the same pause checks as their
+        // single-key entry points.
+        match multisig::execute(e, id) {
+            Action::Mint(account, amount) => {
+                pausable::when_not_paused(e);
+                pausing::when_not_paused(e, PauseCategory::Mint);
+                freeze::ensure_not_frozen(e, &account);
+                allowlist::ensure_allowed(e, &account);
+                supply::record_mint(e, amount);
+                Base::mint(e, &account, amount);
+            }
+            Action::CreateVesting(beneficiary, total, start_ledger, cliff_ledger, end_ledger) => {
+                pausable::when_not_paused(e);
+                pausing::when_not_paused(e, PauseCategory::Mint);
+                Self::mint_vesting(e, &beneficiary, total, start_ledger, cliff_ledger, end_ledger);
+            }
+            Action::Release(job_id) => {
+                pausable::when_not_paused(e);
+                pausing::when_not_paused(e, PauseCategory::EscrowOut);
+                Self::release_escrow(e, job_id);
+            }
+            Action::ScheduleUpgrade(wasm_hash) => {
+                upgrade::schedule_upgrade(e, &wasm_hash);
+            }
+            Action::CancelUpgrade => upgrade::cancel_upgrade(e),
+            Action::ExecuteUpgrade => {
+                let new_wasm_hash = upgrade::take_ready_upgrade(e);
+                e.deployer().update_current_contract_wasm(new_wasm_hash);
+            }
+            Action::Migrate => migration::migrate(e),
+            Action::Pause => pausable::pause(e),
+            Action::Unpause => pausable::unpause(e),
+            Action::PauseCategory(category) => pausing::pause(e, category),
+            Action::UnpauseCategory(category) => pausing::unpause(e, category),
+            Action::SetSigners(signers, threshold) => {
+                multisig::set_signers(e, &signers, threshold);
+            }
+        }
+    }
+
+    pub fn get_proposal(e: &Env, id: u32) -> Proposal {
+        multisig::read_proposal(e, id)
+    }
+
+    pub fn proposal_count(e: &Env) -> u32 {
+        multisig::proposal_count(e)
+    }
+
+    pub fn list_proposals(e: &Env, start: u32, limit: u32) -> Vec<Proposal> {
+        multisig::list_proposals(e, start, limit)
+    }
+
+    #[only_role(operator, "freezer")]
+    pub fn freeze(e: &Env, account: Address, operator: Address) {
+        freeze::freeze(e, &account);
+    }
+
+    #[only_role(operator, "freezer")]
+    pub fn unfreeze(e: &Env, account: Address, operator: Address) {
+        freeze::unfreeze(e, &account);
+    }
+
+    pub fn is_frozen(e: &Env, account: Address) -> bool {
+        freeze::is_frozen(e, &account)
+    }
+
+    #[only_role(operator, "verifier")]
+    pub fn set_allowlist_enabled(e: &Env, enabled: bool, operator: Address) {
+        allowlist::set_enabled(e, enabled);
+    }
+
+    pub fn allowlist_enabled(e: &Env) -> bool {
+        allowlist::is_enabled(e)
+    }
+
+    #[only_role(operator, "verifier")]
+    pub fn allow(e: &Env, accounts: Vec<Address>, operator: Address) {
+        allowlist::allow(e, &accounts);
+    }
+
+    #[only_role(operator, "verifier")]
+    pub fn disallow(e: &Env, accounts: Vec<Address>, operator: Address) {
+        allowlist::disallow(e, &accounts);
+    }
+
+    pub fn is_allowed(e: &Env, account: Address) -> bool {
+        allowlist::is_allowed(e, &account)
+    }
+
+    pub fn get_escrow(e: &Env, job_id: u64) -> Escrow {
+        escrow::read_escrow(e, job_id)
+    }
+
+    pub fn escrow_manager(e: &Env, job_id: u64) -> Option<Address> {
+        escrow::manager(e, job_id)
+    }
+
+    pub fn escrow_count(e: &Env) -> u32 {
+        escrow::escrow_count(e)
+    }
+
+    pub fn list_escrows(e: &Env, start: u32, limit: u32) -> Vec<Escrow> {
+        escrow::list_escrows(e, start, limit)
+    }
+
+    pub fn owner(e: &Env) -> Option<Address> {
+        // The owner is the access control admin, `None` once renounced.
+        access_control::get_admin(e)
+    }
+
+    pub fn transfer_ownership(e: &Env, new_owner: Address, live_until_ledger: u32) {
+        // Two-step transfer: `new_owner` only becomes the owner by calling
+        // `accept_ownership` before `live_until_ledger`. Passing `0` cancels
+        // the pending transfer. Requires the current owner's authorization.
+        access_control::transfer_admin_role(e, &new_owner, live_until_ledger);
+    }
+
+    pub fn accept_ownership(e: &Env) {
+        // Requires the authorization of the pending owner. Roles granted to
+        // the previous owner stay in place until the new owner revokes them.
+        access_control::accept_admin_transfer(e);
+    }
+
+    pub fn renounce_ownership(e: &Env) {
+        // Leaves the contract without an owner for good: roles can no longer
+        // be granted or revoked, and the admin-only settings are frozen.
+        access_control::renounce_admin(e);
+    }
+
+    #[only_role(operator, "upgrader")]
+    pub fn propose_upgrade(e: &Env, new_wasm_hash: BytesN<32>, operator: Address) -> u32 {
+        multisig::ensure_single_key(e);
+        // Code changes are timelocked: the proposal is announced right away
+        // but can only be executed `UPGRADE_DELAY` ledgers later. Returns the
+        // first ledger at which it can be executed.
+        upgrade::schedule_upgrade(e, &new_wasm_hash).executable_ledger
+    }
+
+    #[only_role(operator, "upgrader")]
+    pub fn cancel_upgrade(e: &Env, operator: Address) {
+        multisig::ensure_single_key(e);
+        upgrade::cancel_upgrade(e);
+    }
+
+    #[only_role(operator, "upgrader")]
+    pub fn execute_upgrade(e: &Env, operator: Address) {
+        multisig::ensure_single_key(e);
+        let new_wasm_hash = upgrade::take_ready_upgrade(e);
+        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    pub fn pending_upgrade(e: &Env) -> Option<PendingUpgrade> {
+        upgrade::pending_upgrade(e)
+    }
+
+    #[only_role(operator, "upgrader")]
+    pub fn migrate(e: &Env, operator: Address) {
+        multisig::ensure_single_key(e);
+        // Run once by the new code after `execute_upgrade`, to bring the
+        // stored data up to its layout. Replays fail with `AlreadyMigrated`.
+        migration::migrate(e);
+    }
+
+    pub fn version(e: &Env) -> u32 {
+        migration::version(e)
+    }
+}
+
+impl ExampleContract {
+    /// Moves `amount` of tokens from `from` into the contract balance.
+    ///
+    /// Authorization for `from` must already have been checked by the caller.
+    fn pay_in(e: &Env, from: &Address, amount: i128) {
         let contract_addr: Address = e.current_contract_address();
-        Base::transfer(e, &contract_addr, &to, amount);
+        Base::update(e, Some(from), Some(&contract_addr), amount);
+        emit_transfer(e, from, &contract_addr, amount);
     }
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let owner: Address = env.storage().instance().get(&OWNER).unwrap();
-        owner.require_auth();
-    
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    /// Moves `amount` of escrowed tokens from the contract balance to `to`.
+    ///
+    /// The contract is the holder of the funds, so the transfer is applied
+    /// directly instead of going through `Base::transfer`, which would ask the
+    /// contract to authorize itself.
+    fn pay_out(e: &Env, to: &Address, amount: i128) {
+        let contract_addr: Address = e.current_contract_address();
+        Base::update(e, Some(&contract_addr), Some(to), amount);
+        emit_transfer(e, &contract_addr, to, amount);
+    }
+
+    /// Mints a vesting grant into the contract balance, under the same limits
+    /// as `mint`, to be paid out to `beneficiary` as it vests. Returns the id
+    /// of the schedule.
+    ///
+    /// Authorization must already have been checked by the caller.
+    fn mint_vesting(
+        e: &Env,
+        beneficiary: &Address,
+        total: i128,
+        start_ledger: u32,
+        cliff_ledger: u32,
+        end_ledger: u32,
+    ) -> u32 {
+        allowlist::ensure_allowed(e, beneficiary);
+        let id =
+            vesting::create_schedule(e, beneficiary, total, start_ledger, cliff_ledger, end_ledger);
+        supply::record_mint(e, total);
+        Base::mint(e, &e.current_contract_address(), total);
+        id
+    }
+
+    /// Releases whatever is still locked for `job_id` to the job's worker and
+    /// returns the released amount, fee included.
+    ///
+    /// Authorization must already have been checked by the caller.
+    fn release_escrow(e: &Env, job_id: u64) -> i128 {
+        let mut escrow = escrow::read_escrow(e, job_id);
+        let remaining = escrow::settle_remaining(e, &mut escrow);
+        escrow::write_escrow(e, &escrow);
+
+        let worker = escrow::worker(e, &escrow);
+        let fee = Self::pay_worker(e, &escrow, &worker, remaining);
+        EscrowReleased { job_id, worker, amount: remaining, fee }.publish(e);
+        remaining
+    }
+
+    /// Pays `amount` accrued on `stream` to its worker, minus the platform
+    /// fee which goes to the treasury.
+    ///
+    /// Panics if `amount` is positive and the worker is frozen, or the
+    /// allowlist is enforced and either party of the stream is not on it.
+    fn pay_stream_worker(e: &Env, stream: &Stream, amount: i128) {
+        if amount > 0 {
+            freeze::ensure_not_frozen(e, &stream.worker);
+            allowlist::ensure_allowed(e, &stream.employer);
+            allowlist::ensure_allowed(e, &stream.worker);
+            let fee = fee::compute_fee(e, amount);
+            if fee > 0 {
+                let config = fee::fee_config(e).expect("fee config should be set");
+                Self::pay_out(e, &config.treasury, fee);
+                StreamFeeTaken { stream_id: stream.id, treasury: config.treasury, amount: fee }
+                    .publish(e);
+            }
+            Self::pay_out(e, &stream.worker, amount - fee);
+        }
+    }
+
+    /// Returns true if [`Self::pay_stream_worker`] can pay the worker of
+    /// `stream`.
+    fn stream_worker_payable(e: &Env, stream: &Stream) -> bool {
+        let allowed = !allowlist::is_enabled(e)
+            || (allowlist::is_allowed(e, &stream.employer)
+                && allowlist::is_allowed(e, &stream.worker));
+        !freeze::is_frozen(e, &stream.worker) && allowed
+    }
+
+    /// Panics if a batch has more than [`MAX_BATCH_SIZE`] entries.
+    fn ensure_batch_size(e: &Env, len: u32) {
+        if len > MAX_BATCH_SIZE {
+            panic_with_error!(e, ExampleContractError::BatchTooLarge);
+        }
+    }
+
+    /// Pays `amount` of escrowed tokens for completed work to `worker`, minus
+    /// the platform fee which goes to the treasury, and credits the worker's
+    /// reputation. Returns the fee taken.
+    ///
+    /// Panics if `worker` is frozen, or if the allowlist is enforced and
+    /// either party of the job is not on it.
+    fn pay_worker(e: &Env, escrow: &Escrow, worker: &Address, amount: i128) -> i128 {
+        freeze::ensure_not_frozen(e, worker);
+        allowlist::ensure_allowed(e, &escrow.employer);
+        allowlist::ensure_allowed(e, worker);
+        let fee = fee::compute_fee(e, amount);
+        if fee > 0 {
+            let config = fee::fee_config(e).expect("fee config should be set");
+            Self::pay_out(e, &config.treasury, fee);
+            FeeTaken { job_id: escrow.job_id, treasury: config.treasury, amount: fee }.publish(e);
+        }
+        Self::pay_out(e, worker, amount - fee);
+        reputation::record_payment(e, escrow, worker, amount - fee);
+        fee
     }
 }
 
@@ -80,27 +963,70 @@ impl Pausable for ExampleContract {
         pausable::paused(e)
     }
 
+    #[only_role(caller, "pauser")]
     fn pause(e: &Env, caller: Address) {
-        caller.require_auth();
-        let owner: Address = e.storage().instance().get(&OWNER).expect("owner should be set");
-        if owner != caller {
-            panic_with_error!(e, ExampleContractError::Unauthorized);
-        }
-
+        multisig::ensure_single_key(e);
         pausable::pause(e);
     }
 
+    #[only_role(caller, "pauser")]
     fn unpause(e: &Env, caller: Address) {
-        caller.require_auth();
-        let owner: Address = e.storage().instance().get(&OWNER).expect("owner should be set");
-        if owner != caller {
-            panic_with_error!(e, ExampleContractError::Unauthorized);
-        }
-
+        multisig::ensure_single_key(e);
         pausable::unpause(e);
     }
 }
 
+#[contractimpl]
+impl AccessControl for ExampleContract {
+    fn has_role(e: &Env, account: Address, role: Symbol) -> Option<u32> {
+        access_control::has_role(e, &account, &role)
+    }
+
+    fn get_role_member_count(e: &Env, role: Symbol) -> u32 {
+        access_control::get_role_member_count(e, &role)
+    }
+
+    fn get_role_member(e: &Env, role: Symbol, index: u32) -> Address {
+        access_control::get_role_member(e, &role, index)
+    }
+
+    fn get_role_admin(e: &Env, role: Symbol) -> Option<Symbol> {
+        access_control::get_role_admin(e, &role)
+    }
+
+    fn get_admin(e: &Env) -> Option<Address> {
+        access_control::get_admin(e)
+    }
+
+    fn grant_role(e: &Env, caller: Address, account: Address, role: Symbol) {
+        access_control::grant_role(e, &caller, &account, &role);
+    }
+
+    fn revoke_role(e: &Env, caller: Address, account: Address, role: Symbol) {
+        access_control::revoke_role(e, &caller, &account, &role);
+    }
+
+    fn renounce_role(e: &Env, caller: Address, role: Symbol) {
+        access_control::renounce_role(e, &caller, &role);
+    }
+
+    fn transfer_admin_role(e: &Env, new_admin: Address, live_until_ledger: u32) {
+        access_control::transfer_admin_role(e, &new_admin, live_until_ledger);
+    }
+
+    fn accept_admin_transfer(e: &Env) {
+        access_control::accept_admin_transfer(e);
+    }
+
+    fn set_role_admin(e: &Env, role: Symbol, admin_role: Symbol) {
+        access_control::set_role_admin(e, &role, &admin_role);
+    }
+
+    fn renounce_admin(e: &Env) {
+        access_control::renounce_admin(e);
+    }
+}
+
 #[contractimpl]
 impl FungibleToken for ExampleContract {
     type ContractType = Base;
@@ -119,11 +1045,22 @@ impl FungibleToken for ExampleContract {
 
     #[when_not_paused]
     fn transfer(e: &Env, from: Address, to: Address, amount: i128) {
+        pausing::when_not_paused(e, PauseCategory::Transfers);
+        freeze::ensure_not_frozen(e, &from);
+        freeze::ensure_not_frozen(e, &to);
+        allowlist::ensure_allowed(e, &from);
+        allowlist::ensure_allowed(e, &to);
         Self::ContractType::transfer(e, &from, &to, amount);
     }
 
     #[when_not_paused]
     fn transfer_from(e: &Env, spender: Address, from: Address, to: Address, amount: i128) {
+        pausing::when_not_paused(e, PauseCategory::Transfers);
+        freeze::ensure_not_frozen(e, &spender);
+        freeze::ensure_not_frozen(e, &from);
+        freeze::ensure_not_frozen(e, &to);
+        allowlist::ensure_allowed(e, &from);
+        allowlist::ensure_allowed(e, &to);
         Self::ContractType::transfer_from(e, &spender, &from, &to, amount);
     }
 
@@ -148,11 +1085,16 @@ impl FungibleToken for ExampleContract {
 impl FungibleBurnable for ExampleContract {
     #[when_not_paused]
     fn burn(e: &Env, from: Address, amount: i128) {
+        pausing::when_not_paused(e, PauseCategory::Burn);
+        freeze::ensure_not_frozen(e, &from);
         Self::ContractType::burn(e, &from, amount)
     }
 
     #[when_not_paused]
     fn burn_from(e: &Env, spender: Address, from: Address, amount: i128) {
+        pausing::when_not_paused(e, PauseCategory::Burn);
+        freeze::ensure_not_frozen(e, &spender);
+        freeze::ensure_not_frozen(e, &from);
         Self::ContractType::burn_from(e, &spender, &from, amount)
     }
 }
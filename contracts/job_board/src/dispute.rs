@@ -0,0 +1,184 @@
+//! Dispute resolution for escrowed jobs.
+//!
+//! Either party of a `Locked` escrow can open a dispute, which freezes the
+//! escrow: no release, milestone delivery or approval is possible until an
+//! account holding the `arbiter` role rules on it. The ruling splits whatever
+//! is still held for the job between the worker and the employer.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env};
+use stellar_access::access_control;
+
+use crate::{
+    contract::{ExampleContractError, ARBITER_ROLE},
+    escrow::{self, Escrow, EscrowStatus, ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// Denominator of the worker share passed to [`resolve_dispute`].
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// A dispute opened on an escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub job_id: u64,
+    pub opened_by: Address,
+    pub opened_ledger: u32,
+    /// Set once the dispute has been ruled on.
+    pub arbiter: Option<Address>,
+    /// Share of the disputed funds awarded to the worker, in basis points.
+    pub worker_bps: Option<u32>,
+}
+
+/// Storage keys for the dispute subsystem.
+#[contracttype]
+pub enum DisputeStorageKey {
+    /// Maps a job id to its [`Dispute`].
+    Dispute(u64),
+}
+
+/// Returns true if `account` holds the `arbiter` role.
+pub fn is_arbiter(e: &Env, account: &Address) -> bool {
+    access_control::has_role(e, account, &ARBITER_ROLE).is_some()
+}
+
+/// Returns the dispute opened on `job_id`, if any.
+pub fn get_dispute(e: &Env, job_id: u64) -> Option<Dispute> {
+    let key = DisputeStorageKey::Dispute(job_id);
+    let dispute = e.storage().persistent().get::<_, Dispute>(&key);
+    if dispute.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    dispute
+}
+
+fn write_dispute(e: &Env, dispute: &Dispute) {
+    let key = DisputeStorageKey::Dispute(dispute.job_id);
+    e.storage().persistent().set(&key, dispute);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+}
+
+/// Moves `escrow` to `Disputed` and records who opened the dispute.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::WorkerNotAssigned`] - When no worker has been
+///   assigned yet.
+/// * [`ExampleContractError::Unauthorized`] - When `caller` is neither the
+///   employer nor the worker of the escrow.
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+///
+/// # Events
+///
+/// * topics - `["dispute_opened", job_id: u64, opened_by: Address]`
+/// * data - `[]`
+///
+/// # Notes
+///
+/// Authorization for `caller` is expected to be checked by the caller.
+pub fn open_dispute(e: &Env, caller: &Address, escrow: &mut Escrow) {
+    // A budget without a worker has nobody to rule for, and a dispute would
+    // keep the employer from refunding it.
+    let worker = escrow::worker(e, escrow);
+    if *caller != escrow.employer && *caller != worker {
+        panic_with_error!(e, ExampleContractError::Unauthorized);
+    }
+    if escrow.status != EscrowStatus::Locked {
+        panic_with_error!(e, ExampleContractError::EscrowNotLocked);
+    }
+
+    escrow.status = EscrowStatus::Disputed;
+    write_dispute(
+        e,
+        &Dispute {
+            job_id: escrow.job_id,
+            opened_by: caller.clone(),
+            opened_ledger: e.ledger().sequence(),
+            arbiter: None,
+            worker_bps: None,
+        },
+    );
+
+    DisputeOpened { job_id: escrow.job_id, opened_by: caller.clone() }.publish(e);
+}
+
+/// Rules on a `Disputed` escrow and returns the `(worker, employer)` amounts
+/// that have to be paid out. The escrow is marked `Resolved`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::Unauthorized`] - When `arbiter` does not hold
+///   the `arbiter` role.
+/// * [`ExampleContractError::EscrowNotDisputed`] - When the escrow is not
+///   `Disputed`.
+/// * [`ExampleContractError::InvalidSplit`] - When `worker_bps` exceeds
+///   [`BPS_DENOMINATOR`].
+///
+/// # Events
+///
+/// * topics - `["dispute_resolved", job_id: u64, arbiter: Address]`
+/// * data - `[worker_amount: i128, employer_amount: i128]`
+///
+/// # Notes
+///
+/// Authorization for `arbiter` is expected to be checked by the caller.
+pub fn resolve_dispute(
+    e: &Env,
+    arbiter: &Address,
+    escrow: &mut Escrow,
+    worker_bps: u32,
+) -> (i128, i128) {
+    if !is_arbiter(e, arbiter) {
+        panic_with_error!(e, ExampleContractError::Unauthorized);
+    }
+    if escrow.status != EscrowStatus::Disputed {
+        panic_with_error!(e, ExampleContractError::EscrowNotDisputed);
+    }
+    if worker_bps > BPS_DENOMINATOR {
+        panic_with_error!(e, ExampleContractError::InvalidSplit);
+    }
+
+    let disputed = escrow::settle(escrow, EscrowStatus::Resolved);
+    let worker_amount = disputed * worker_bps as i128 / BPS_DENOMINATOR as i128;
+    let employer_amount = disputed - worker_amount;
+
+    let mut dispute = get_dispute(e, escrow.job_id).expect("dispute should be set");
+    dispute.arbiter = Some(arbiter.clone());
+    dispute.worker_bps = Some(worker_bps);
+    write_dispute(e, &dispute);
+
+    DisputeResolved {
+        job_id: escrow.job_id,
+        arbiter: arbiter.clone(),
+        worker_amount,
+        employer_amount,
+    }
+    .publish(e);
+
+    (worker_amount, employer_amount)
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when a party opens a dispute on an escrow.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpened {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub opened_by: Address,
+}
+
+/// Event emitted when an arbiter rules on a dispute, which pays out the
+/// disputed funds.
+#[contractevent(topics = ["dispute_resolved", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub arbiter: Address,
+    pub worker_amount: i128,
+    pub employer_amount: i128,
+}
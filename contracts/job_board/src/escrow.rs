@@ -0,0 +1,652 @@
+//! Per-job escrow bookkeeping.
+//!
+//! Every call to `lock` earmarks the deposited tokens for a single job id by
+//! writing an [`Escrow`] record to persistent storage. The tokens themselves
+//! are still held by the contract address, but payouts are only ever made
+//! against a record, so the contract balance can always be reconciled with
+//! the sum of the open escrows.
+//!
+//! An escrow can optionally be split into milestones at lock time. The worker
+//! marks each milestone as delivered and the employer (or an arbiter) approves
+//! it, which releases exactly that tranche.
+//!
+//! A budget can also be locked before the worker is known, e.g. when a job is
+//! published. Such an escrow cannot be delivered or released until the
+//! employer assigns a worker, and the employer can take it back at any time.
+//! Budget escrows get their job id from the contract, out of a range that
+//! `lock` cannot use, so another account cannot take the id first. A budget
+//! can also be locked on behalf of a manager, such as the job registry, which
+//! then takes the employer's place for assigning the worker and refunding the
+//! budget.
+//!
+//! The events that move escrowed funds (`escrow_locked`, `escrow_released`,
+//! `milestone_released`, `escrow_refunded`, `dispute_resolved` in
+//! [`crate::dispute`] and `fee_taken` in [`crate::fee`]) are versioned: the
+//! event name is followed by a version topic such as `v1`, which is bumped
+//! whenever their topics or data change.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::contract::ExampleContractError;
+
+pub const DAY_IN_LEDGERS: u32 = 17280;
+pub const ESCROW_EXTEND_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const ESCROW_TTL_THRESHOLD: u32 = ESCROW_EXTEND_AMOUNT - DAY_IN_LEDGERS;
+
+/// Lifecycle of an escrow record.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    /// Funds are held by the contract for the job.
+    Locked,
+    /// Funds have been paid out to the worker.
+    Released,
+    /// A party opened a dispute, funds are frozen until an arbiter rules.
+    Disputed,
+    /// An arbiter split the funds between the worker and the employer.
+    Resolved,
+    /// The deadline passed without delivery and the employer was refunded.
+    Refunded,
+}
+
+/// Lifecycle of a single milestone.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneStatus {
+    /// Waiting for the worker to deliver.
+    Pending,
+    /// Delivered by the worker, waiting for approval.
+    Delivered,
+    /// Approved and paid out to the worker.
+    Released,
+}
+
+/// A tranche of an escrow that is released on its own.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub status: MilestoneStatus,
+}
+
+/// Funds earmarked for a single job.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub job_id: u64,
+    pub employer: Address,
+    /// `None` while the budget is locked for a job that has not hired yet.
+    pub worker: Option<Address>,
+    pub amount: i128,
+    /// Part of `amount` already paid out to the worker.
+    pub released: i128,
+    pub status: EscrowStatus,
+    pub created_ledger: u32,
+    /// Ledger by which the work is due.
+    pub deadline_ledger: u32,
+    /// Ledgers after `deadline_ledger` during which the worker can still
+    /// deliver, snapshotted from the contract setting at lock time.
+    pub grace_period: u32,
+    /// Set once the worker submitted a single-payment delivery.
+    pub delivered: bool,
+    /// Empty for single-payment escrows.
+    pub milestones: Vec<Milestone>,
+}
+
+/// Storage keys for the escrow records.
+#[contracttype]
+pub enum EscrowStorageKey {
+    /// Maps a job id to its [`Escrow`].
+    Escrow(u64),
+    /// Number of escrows ever created.
+    Count,
+    /// Maps a creation index to a job id, used for enumeration.
+    Index(u32),
+    /// Grace period applied to new escrows, in ledgers.
+    GracePeriod,
+    /// Number of budget escrows ever created.
+    BudgetCount,
+    /// Maps the job id of a budget escrow to the account managing it, only
+    /// set for budgets locked on behalf of a manager.
+    Manager(u64),
+}
+
+/// Grace period applied to new escrows until the owner configures one.
+pub const DEFAULT_GRACE_PERIOD: u32 = DAY_IN_LEDGERS;
+
+/// First job id handed out to budget escrows. Ids from here on are reserved
+/// for them and rejected by [`create_escrow`].
+pub const FIRST_BUDGET_JOB_ID: u64 = 1 << 63;
+
+/// Returns the escrow for `job_id`, if any.
+///
+/// # Arguments
+///
+/// * `e` - Access to the Soroban environment.
+/// * `job_id` - The job the escrow was created for.
+pub fn get_escrow(e: &Env, job_id: u64) -> Option<Escrow> {
+    let key = EscrowStorageKey::Escrow(job_id);
+    let escrow = e.storage().persistent().get::<_, Escrow>(&key);
+    if escrow.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    escrow
+}
+
+/// Returns the escrow for `job_id`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotFound`] - When no escrow exists.
+pub fn read_escrow(e: &Env, job_id: u64) -> Escrow {
+    get_escrow(e, job_id)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::EscrowNotFound))
+}
+
+/// Stores `escrow` under its job id.
+pub fn write_escrow(e: &Env, escrow: &Escrow) {
+    let key = EscrowStorageKey::Escrow(escrow.job_id);
+    e.storage().persistent().set(&key, escrow);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+}
+
+/// Returns the number of escrows ever created.
+pub fn escrow_count(e: &Env) -> u32 {
+    e.storage().instance().get(&EscrowStorageKey::Count).unwrap_or(0)
+}
+
+/// Returns the grace period applied to new escrows, in ledgers.
+pub fn grace_period(e: &Env) -> u32 {
+    e.storage().instance().get(&EscrowStorageKey::GracePeriod).unwrap_or(DEFAULT_GRACE_PERIOD)
+}
+
+/// Sets the grace period applied to escrows created from now on. Existing
+/// escrows keep the grace period they were created with.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn set_grace_period(e: &Env, ledgers: u32) {
+    e.storage().instance().set(&EscrowStorageKey::GracePeriod, &ledgers);
+}
+
+/// Returns the manager of the budget escrow for `job_id`, if any.
+pub fn manager(e: &Env, job_id: u64) -> Option<Address> {
+    let key = EscrowStorageKey::Manager(job_id);
+    let manager = e.storage().persistent().get::<_, Address>(&key);
+    if manager.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    manager
+}
+
+/// Returns the account that authorizes assigning the worker of `escrow` and
+/// refunding it while it has none: its manager if set, the employer otherwise.
+pub fn budget_authority(e: &Env, escrow: &Escrow) -> Address {
+    manager(e, escrow.job_id).unwrap_or_else(|| escrow.employer.clone())
+}
+
+/// Returns the last ledger at which the worker can still deliver.
+pub fn delivery_cutoff(escrow: &Escrow) -> u32 {
+    escrow.deadline_ledger.saturating_add(escrow.grace_period)
+}
+
+/// Creates a new `Locked` escrow record and registers it for enumeration.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `amount` is not positive.
+/// * [`ExampleContractError::InvalidDeadline`] - When `deadline_ledger` is not
+///   in the future.
+/// * [`ExampleContractError::EscrowAlreadyExists`] - When `job_id` is taken.
+/// * [`ExampleContractError::JobIdReserved`] - When `job_id` is in the range
+///   reserved for budget escrows.
+/// * [`ExampleContractError::EmployerIsWorker`] - When `worker` is the
+///   employer, who could otherwise pay themselves to build a reputation.
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, moving the tokens is up to the
+/// caller.
+pub fn create_escrow(
+    e: &Env,
+    job_id: u64,
+    employer: &Address,
+    worker: &Address,
+    amount: i128,
+    deadline_ledger: u32,
+) -> Escrow {
+    ensure_not_reserved(e, job_id);
+    store_new_escrow(e, job_id, employer, Some(worker), amount, deadline_ledger, Vec::new(e))
+}
+
+/// Creates a new `Locked` escrow whose worker will be assigned later, under
+/// the next job id reserved for budget escrows.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `amount` is not positive.
+/// * [`ExampleContractError::InvalidDeadline`] - When `deadline_ledger` is not
+///   in the future.
+pub fn create_budget_escrow(
+    e: &Env,
+    employer: &Address,
+    manager: Option<&Address>,
+    amount: i128,
+    deadline_ledger: u32,
+) -> Escrow {
+    let count: u64 = e.storage().instance().get(&EscrowStorageKey::BudgetCount).unwrap_or(0);
+    e.storage().instance().set(&EscrowStorageKey::BudgetCount, &(count + 1));
+    let job_id = FIRST_BUDGET_JOB_ID + count;
+    let escrow = store_new_escrow(e, job_id, employer, None, amount, deadline_ledger, Vec::new(e));
+    if let Some(manager) = manager {
+        let key = EscrowStorageKey::Manager(job_id);
+        e.storage().persistent().set(&key, manager);
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    escrow
+}
+
+/// Returns the worker of `escrow`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::WorkerNotAssigned`] - When no worker has been
+///   assigned yet.
+pub fn worker(e: &Env, escrow: &Escrow) -> Address {
+    escrow
+        .worker
+        .clone()
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::WorkerNotAssigned))
+}
+
+/// Binds a `Locked` budget escrow to `worker`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::WorkerAlreadyAssigned`] - When the escrow already
+///   has a worker.
+/// * [`ExampleContractError::EmployerIsWorker`] - When `worker` is the
+///   employer.
+/// * [`ExampleContractError::HiringWindowClosed`] - When the deadline and
+///   grace period have passed, leaving the worker no time to deliver.
+///
+/// # Events
+///
+/// * topics - `["worker_assigned", job_id: u64, worker: Address]`
+/// * data - `[]`
+pub fn assign_worker(e: &Env, escrow: &mut Escrow, worker: &Address) {
+    ensure_locked(e, escrow);
+    if escrow.worker.is_some() {
+        panic_with_error!(e, ExampleContractError::WorkerAlreadyAssigned);
+    }
+    if *worker == escrow.employer {
+        panic_with_error!(e, ExampleContractError::EmployerIsWorker);
+    }
+    if e.ledger().sequence() > delivery_cutoff(escrow) {
+        panic_with_error!(e, ExampleContractError::HiringWindowClosed);
+    }
+    escrow.worker = Some(worker.clone());
+
+    WorkerAssigned { job_id: escrow.job_id, worker: worker.clone() }.publish(e);
+}
+
+/// Creates a new `Locked` escrow split into one milestone per entry of
+/// `amounts`. The escrow amount is the sum of the milestones.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `amounts` is empty, any
+///   amount is not positive or the sum overflows.
+/// * [`ExampleContractError::InvalidDeadline`] - When `deadline_ledger` is not
+///   in the future.
+/// * [`ExampleContractError::EscrowAlreadyExists`] - When `job_id` is taken.
+/// * [`ExampleContractError::JobIdReserved`] - When `job_id` is in the range
+///   reserved for budget escrows.
+/// * [`ExampleContractError::EmployerIsWorker`] - When `worker` is the
+///   employer.
+pub fn create_milestone_escrow(
+    e: &Env,
+    job_id: u64,
+    employer: &Address,
+    worker: &Address,
+    amounts: &Vec<i128>,
+    deadline_ledger: u32,
+) -> Escrow {
+    ensure_not_reserved(e, job_id);
+    if amounts.is_empty() {
+        panic_with_error!(e, ExampleContractError::InvalidAmount);
+    }
+
+    let mut total: i128 = 0;
+    let mut milestones = Vec::new(e);
+    for amount in amounts.iter() {
+        if amount <= 0 {
+            panic_with_error!(e, ExampleContractError::InvalidAmount);
+        }
+        total = total
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::InvalidAmount));
+        milestones.push_back(Milestone { amount, status: MilestoneStatus::Pending });
+    }
+
+    store_new_escrow(e, job_id, employer, Some(worker), total, deadline_ledger, milestones)
+}
+
+fn store_new_escrow(
+    e: &Env,
+    job_id: u64,
+    employer: &Address,
+    worker: Option<&Address>,
+    amount: i128,
+    deadline_ledger: u32,
+    milestones: Vec<Milestone>,
+) -> Escrow {
+    if amount <= 0 {
+        panic_with_error!(e, ExampleContractError::InvalidAmount);
+    }
+    if deadline_ledger <= e.ledger().sequence() {
+        panic_with_error!(e, ExampleContractError::InvalidDeadline);
+    }
+    if e.storage().persistent().has(&EscrowStorageKey::Escrow(job_id)) {
+        panic_with_error!(e, ExampleContractError::EscrowAlreadyExists);
+    }
+    if worker == Some(employer) {
+        panic_with_error!(e, ExampleContractError::EmployerIsWorker);
+    }
+
+    let escrow = Escrow {
+        job_id,
+        employer: employer.clone(),
+        worker: worker.cloned(),
+        amount,
+        released: 0,
+        status: EscrowStatus::Locked,
+        created_ledger: e.ledger().sequence(),
+        deadline_ledger,
+        grace_period: grace_period(e),
+        delivered: false,
+        milestones,
+    };
+    write_escrow(e, &escrow);
+
+    let index = escrow_count(e);
+    let key = EscrowStorageKey::Index(index);
+    e.storage().persistent().set(&key, &job_id);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    e.storage().instance().set(&EscrowStorageKey::Count, &(index + 1));
+
+    EscrowLocked {
+        job_id,
+        employer: employer.clone(),
+        worker: worker.cloned(),
+        amount,
+        deadline_ledger,
+    }
+    .publish(e);
+    escrow
+}
+
+/// Returns up to `limit` escrows in creation order, starting at `start`.
+pub fn list_escrows(e: &Env, start: u32, limit: u32) -> Vec<Escrow> {
+    let mut escrows = Vec::new(e);
+    let end = start.saturating_add(limit).min(escrow_count(e));
+    for index in start..end {
+        let key = EscrowStorageKey::Index(index);
+        let job_id: u64 = e.storage().persistent().get(&key).expect("escrow index should be set");
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+        escrows.push_back(read_escrow(e, job_id));
+    }
+    escrows
+}
+
+/// Marks every outstanding part of a `Locked` escrow as paid and returns the
+/// amount that still has to be transferred to the worker.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::WorkerNotAssigned`] - When no worker has been
+///   assigned yet.
+pub fn settle_remaining(e: &Env, escrow: &mut Escrow) -> i128 {
+    ensure_locked(e, escrow);
+    worker(e, escrow);
+
+    settle(escrow, EscrowStatus::Released)
+}
+
+/// Closes `escrow` with `status` and returns the amount that was still held
+/// for the job. Outstanding milestones are only marked as paid when the
+/// escrow is `Released` to the worker.
+///
+/// # Notes
+///
+/// No state transition checks are performed, those are up to the caller.
+pub fn settle(escrow: &mut Escrow, status: EscrowStatus) -> i128 {
+    let remaining = escrow.amount - escrow.released;
+    if status == EscrowStatus::Released {
+        for index in 0..escrow.milestones.len() {
+            let mut milestone = escrow.milestones.get_unchecked(index);
+            milestone.status = MilestoneStatus::Released;
+            escrow.milestones.set(index, milestone);
+        }
+    }
+    escrow.released = escrow.amount;
+    escrow.status = status;
+    remaining
+}
+
+/// Moves milestone `index` from `Pending` to `Delivered`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::MilestoneNotFound`] - When `index` is out of
+///   range.
+/// * [`ExampleContractError::InvalidMilestoneState`] - When the milestone is
+///   not `Pending`.
+/// * [`ExampleContractError::DeliveryWindowClosed`] - When the deadline and
+///   grace period have passed.
+///
+/// # Events
+///
+/// * topics - `["milestone_delivered", job_id: u64]`
+/// * data - `[index: u32]`
+pub fn deliver_milestone(e: &Env, escrow: &mut Escrow, index: u32) {
+    ensure_locked(e, escrow);
+    ensure_delivery_window_open(e, escrow);
+
+    let mut milestone = read_milestone(e, escrow, index);
+    if milestone.status != MilestoneStatus::Pending {
+        panic_with_error!(e, ExampleContractError::InvalidMilestoneState);
+    }
+    milestone.status = MilestoneStatus::Delivered;
+    escrow.milestones.set(index, milestone);
+
+    MilestoneDelivered { job_id: escrow.job_id, index }.publish(e);
+}
+
+/// Moves milestone `index` from `Delivered` to `Released` and returns the
+/// tranche that has to be transferred to the worker. The escrow itself is
+/// marked `Released` once its last milestone is paid.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::MilestoneNotFound`] - When `index` is out of
+///   range.
+/// * [`ExampleContractError::InvalidMilestoneState`] - When the milestone is
+///   not `Delivered`.
+pub fn release_milestone(e: &Env, escrow: &mut Escrow, index: u32) -> i128 {
+    ensure_locked(e, escrow);
+
+    let mut milestone = read_milestone(e, escrow, index);
+    if milestone.status != MilestoneStatus::Delivered {
+        panic_with_error!(e, ExampleContractError::InvalidMilestoneState);
+    }
+    milestone.status = MilestoneStatus::Released;
+    escrow.milestones.set(index, milestone.clone());
+
+    escrow.released += milestone.amount;
+    if escrow.released == escrow.amount {
+        escrow.status = EscrowStatus::Released;
+    }
+
+    milestone.amount
+}
+
+/// Marks a single-payment escrow as delivered, which protects it from being
+/// refunded until the employer releases or disputes it.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::InvalidMilestoneState`] - When the escrow has
+///   milestones or the delivery was already submitted.
+/// * [`ExampleContractError::DeliveryWindowClosed`] - When the deadline and
+///   grace period have passed.
+pub fn submit_delivery(e: &Env, escrow: &mut Escrow) {
+    ensure_locked(e, escrow);
+    ensure_delivery_window_open(e, escrow);
+    if !escrow.milestones.is_empty() || escrow.delivered {
+        panic_with_error!(e, ExampleContractError::InvalidMilestoneState);
+    }
+    escrow.delivered = true;
+}
+
+/// Closes an expired escrow as `Refunded` and returns the amount that has to
+/// be transferred back to the employer. Escrows without a worker can be
+/// refunded at any time.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotLocked`] - When the escrow is not
+///   `Locked`.
+/// * [`ExampleContractError::RefundNotAvailable`] - When the deadline and
+///   grace period have not passed yet.
+/// * [`ExampleContractError::DeliveryPending`] - When delivered work is still
+///   waiting for approval.
+pub fn refund(e: &Env, escrow: &mut Escrow) -> i128 {
+    ensure_locked(e, escrow);
+    if escrow.worker.is_none() {
+        return settle(escrow, EscrowStatus::Refunded);
+    }
+    if e.ledger().sequence() <= delivery_cutoff(escrow) {
+        panic_with_error!(e, ExampleContractError::RefundNotAvailable);
+    }
+    let delivery_pending = escrow.delivered
+        || escrow.milestones.iter().any(|m| m.status == MilestoneStatus::Delivered);
+    if delivery_pending {
+        panic_with_error!(e, ExampleContractError::DeliveryPending);
+    }
+
+    settle(escrow, EscrowStatus::Refunded)
+}
+
+fn ensure_not_reserved(e: &Env, job_id: u64) {
+    if job_id >= FIRST_BUDGET_JOB_ID {
+        panic_with_error!(e, ExampleContractError::JobIdReserved);
+    }
+}
+
+fn ensure_delivery_window_open(e: &Env, escrow: &Escrow) {
+    if e.ledger().sequence() > delivery_cutoff(escrow) {
+        panic_with_error!(e, ExampleContractError::DeliveryWindowClosed);
+    }
+}
+
+fn ensure_locked(e: &Env, escrow: &Escrow) {
+    if escrow.status != EscrowStatus::Locked {
+        panic_with_error!(e, ExampleContractError::EscrowNotLocked);
+    }
+}
+
+fn read_milestone(e: &Env, escrow: &Escrow, index: u32) -> Milestone {
+    escrow
+        .milestones
+        .get(index)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::MilestoneNotFound))
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when funds are locked for a job. `worker` is unset for
+/// budgets locked before hiring.
+#[contractevent(topics = ["escrow_locked", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowLocked {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub employer: Address,
+    pub worker: Option<Address>,
+    pub amount: i128,
+    pub deadline_ledger: u32,
+}
+
+/// Event emitted when a budget escrow is bound to its worker.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkerAssigned {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub worker: Address,
+}
+
+/// Event emitted when the worker delivers a milestone.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneDelivered {
+    #[topic]
+    pub job_id: u64,
+    pub index: u32,
+}
+
+/// Event emitted when a milestone tranche is paid out to the worker.
+///
+/// `amount` is the full tranche, `fee` the part of it sent to the treasury.
+#[contractevent(topics = ["milestone_released", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneReleased {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub worker: Address,
+    pub index: u32,
+    pub amount: i128,
+    pub fee: i128,
+}
+
+/// Event emitted when an escrow is released to the worker.
+///
+/// `amount` is what was still held for the job, `fee` the part of it sent to
+/// the treasury.
+#[contractevent(topics = ["escrow_released", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowReleased {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub worker: Address,
+    pub amount: i128,
+    pub fee: i128,
+}
+
+/// Event emitted when whatever was still held for a job is refunded to the
+/// employer.
+#[contractevent(topics = ["escrow_refunded", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefunded {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub employer: Address,
+    pub amount: i128,
+}
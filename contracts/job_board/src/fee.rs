@@ -0,0 +1,101 @@
+//! Platform fee taken on escrow releases and stream payouts.
+//!
+//! The admin configures a fee in basis points and the treasury that collects
+//! it. Every payout of completed work to a worker, out of an escrow or a
+//! stream, is reduced by the fee, which is transferred to the treasury in the
+//! same invocation.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env};
+
+use crate::{contract::ExampleContractError, dispute::BPS_DENOMINATOR};
+
+/// Hard upper bound of the platform fee, 10%.
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// Current platform fee configuration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+/// Storage keys for the platform fee.
+#[contracttype]
+pub enum FeeStorageKey {
+    /// Maps to the [`FeeConfig`], unset while no fee is charged.
+    Config,
+}
+
+/// Returns the fee configuration, if a fee has been configured.
+pub fn fee_config(e: &Env) -> Option<FeeConfig> {
+    e.storage().instance().get(&FeeStorageKey::Config)
+}
+
+/// Sets the platform fee and the treasury collecting it.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::FeeTooHigh`] - When `fee_bps` exceeds
+///   [`MAX_FEE_BPS`].
+///
+/// # Events
+///
+/// * topics - `["fee_config_updated", treasury: Address]`
+/// * data - `[fee_bps: u32]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn set_fee_config(e: &Env, fee_bps: u32, treasury: &Address) {
+    if fee_bps > MAX_FEE_BPS {
+        panic_with_error!(e, ExampleContractError::FeeTooHigh);
+    }
+    let config = FeeConfig { fee_bps, treasury: treasury.clone() };
+    e.storage().instance().set(&FeeStorageKey::Config, &config);
+
+    FeeConfigUpdated { treasury: treasury.clone(), fee_bps }.publish(e);
+}
+
+/// Returns the part of `amount` owed to the treasury, `0` when no fee is
+/// configured.
+pub fn compute_fee(e: &Env, amount: i128) -> i128 {
+    match fee_config(e) {
+        Some(config) => amount * config.fee_bps as i128 / BPS_DENOMINATOR as i128,
+        None => 0,
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the admin changes the platform fee.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigUpdated {
+    #[topic]
+    pub treasury: Address,
+    pub fee_bps: u32,
+}
+
+/// Event emitted when the platform fee of a payout is sent to the treasury.
+#[contractevent(topics = ["fee_taken", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTaken {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+/// Event emitted when the platform fee of a stream payout is sent to the
+/// treasury.
+#[contractevent(topics = ["stream_fee_taken", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamFeeTaken {
+    #[topic]
+    pub stream_id: u32,
+    #[topic]
+    pub treasury: Address,
+    pub amount: i128,
+}
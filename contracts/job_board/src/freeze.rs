@@ -0,0 +1,94 @@
+//! Per-account freezing for abuse handling.
+//!
+//! Accounts holding the `freezer` role can freeze an address. A frozen
+//! address can neither send, receive nor burn JBT, cannot lock new escrows,
+//! and cannot be paid out of an escrow, while every other account keeps
+//! working normally.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// Storage keys for frozen accounts.
+#[contracttype]
+pub enum FreezeStorageKey {
+    /// Present for every frozen account.
+    Frozen(Address),
+}
+
+/// Returns true if `account` is frozen.
+pub fn is_frozen(e: &Env, account: &Address) -> bool {
+    let key = FreezeStorageKey::Frozen(account.clone());
+    let frozen = e.storage().persistent().has(&key);
+    if frozen {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    frozen
+}
+
+/// Panics if `account` is frozen.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::AccountFrozen`] - When `account` is frozen.
+pub fn ensure_not_frozen(e: &Env, account: &Address) {
+    if is_frozen(e, account) {
+        panic_with_error!(e, ExampleContractError::AccountFrozen);
+    }
+}
+
+/// Freezes `account`, freezing an already frozen account has no effect.
+///
+/// # Events
+///
+/// * topics - `["account_frozen", account: Address]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn freeze(e: &Env, account: &Address) {
+    let key = FreezeStorageKey::Frozen(account.clone());
+    e.storage().persistent().set(&key, &true);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+    AccountFrozen { account: account.clone() }.publish(e);
+}
+
+/// Unfreezes `account`, unfreezing an account that is not frozen has no
+/// effect.
+///
+/// # Events
+///
+/// * topics - `["account_unfrozen", account: Address]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn unfreeze(e: &Env, account: &Address) {
+    e.storage().persistent().remove(&FreezeStorageKey::Frozen(account.clone()));
+
+    AccountUnfrozen { account: account.clone() }.publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when an account is frozen.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountFrozen {
+    #[topic]
+    pub account: Address,
+}
+
+/// Event emitted when an account is unfrozen.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountUnfrozen {
+    #[topic]
+    pub account: Address,
+}
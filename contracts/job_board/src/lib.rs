@@ -1,6 +1,21 @@
 #![no_std]
 #![allow(dead_code)]
 
-mod contract;
+mod allowlist;
+pub mod contract;
+mod dispute;
+mod escrow;
+mod fee;
+mod freeze;
+mod migration;
+mod multisig;
+mod pausing;
+mod permit;
+mod rating;
+mod reputation;
+mod stream;
+mod supply;
+mod upgrade;
+mod vesting;
 #[cfg(test)]
 mod test;
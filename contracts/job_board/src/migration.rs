@@ -0,0 +1,85 @@
+//! Storage schema versioning.
+//!
+//! The contract records the version of the storage layout it was deployed or
+//! last migrated with. Code that changes the layout bumps
+//! [`CONTRACT_VERSION`] and adds a step to [`migrate`]; after the upgrade is
+//! executed, `migrate` brings the stored data up to date exactly once.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Env};
+
+use crate::contract::ExampleContractError;
+
+/// Storage layout version of this code.
+#[cfg(not(feature = "next-schema"))]
+pub const CONTRACT_VERSION: u32 = 1;
+/// Storage layout version of this code, bumped to exercise migrations in
+/// upgrade tests.
+#[cfg(feature = "next-schema")]
+pub const CONTRACT_VERSION: u32 = 2;
+
+/// Storage keys for the schema version.
+#[contracttype]
+pub enum MigrationStorageKey {
+    /// Version of the storage layout currently in use.
+    Version,
+}
+
+/// Layout of deployments made before the version was tracked.
+const UNTRACKED_VERSION: u32 = 1;
+
+/// Returns the version of the storage layout currently in use.
+pub fn version(e: &Env) -> u32 {
+    e.storage().instance().get(&MigrationStorageKey::Version).unwrap_or(UNTRACKED_VERSION)
+}
+
+/// Records that a fresh deployment uses the layout of this code.
+pub fn set_initial_version(e: &Env) {
+    e.storage().instance().set(&MigrationStorageKey::Version, &CONTRACT_VERSION);
+}
+
+/// Migrates the stored data from the recorded layout to [`CONTRACT_VERSION`].
+///
+/// # Errors
+///
+/// * [`ExampleContractError::AlreadyMigrated`] - When the stored data already
+///   uses the layout of this code.
+/// * [`ExampleContractError::MigrationNotFound`] - When this code has no step
+///   for one of the versions in between.
+///
+/// # Events
+///
+/// * topics - `["migrated", to_version: u32]`
+/// * data - `[from_version: u32]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn migrate(e: &Env) {
+    let from_version = version(e);
+    if from_version >= CONTRACT_VERSION {
+        panic_with_error!(e, ExampleContractError::AlreadyMigrated);
+    }
+    // One arm per layout change, applied in order, e.g.
+    // `1 => migrate_v1_to_v2(e)`. Version 2 only exists to exercise the
+    // upgrade path and keeps the v1 layout.
+    for step in from_version..CONTRACT_VERSION {
+        match step {
+            1 => {}
+            _ => panic_with_error!(e, ExampleContractError::MigrationNotFound),
+        }
+    }
+    e.storage().instance().set(&MigrationStorageKey::Version, &CONTRACT_VERSION);
+
+    Migrated { to_version: CONTRACT_VERSION, from_version }.publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the stored data is migrated to a new layout.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Migrated {
+    #[topic]
+    pub to_version: u32,
+    pub from_version: u32,
+}
@@ -0,0 +1,341 @@
+//! Multisig approval of administrative actions.
+//!
+//! Next to the single-key roles, the owner can configure a set of signers and
+//! a threshold. Any signer can then propose an [`Action`], the other signers
+//! approve it, and once `threshold` distinct signers have approved, the
+//! proposal can be executed. Proposals expire at a ledger chosen by the
+//! proposer and are kept for enumeration after execution.
+//!
+//! Once the signers are configured, the actions they can propose are only
+//! carried out through proposals: the single-key entry points for minting,
+//! vesting grants, releases, upgrades, migrations and pausing fail with
+//! [`ExampleContractError::MultisigRequired`], whichever roles the owner
+//! grants. The signer set itself can afterwards only be changed through a
+//! [`Action::SetSigners`] proposal.
+
+use soroban_sdk::{
+    contractevent, contracttype, panic_with_error, Address, BytesN, Env, Vec,
+};
+
+use crate::{contract::ExampleContractError, escrow::DAY_IN_LEDGERS, pausing::PauseCategory};
+
+pub const PROPOSAL_EXTEND_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const PROPOSAL_TTL_THRESHOLD: u32 = PROPOSAL_EXTEND_AMOUNT - DAY_IN_LEDGERS;
+
+/// Administrative action carried out by an executed proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Mints an amount of tokens to an account.
+    Mint(Address, i128),
+    /// Mints a vesting grant to a beneficiary: the total, then the start,
+    /// cliff and end ledgers of the schedule.
+    CreateVesting(Address, i128, u32, u32, u32),
+    /// Releases the escrow of a job to its worker.
+    Release(u64),
+    /// Schedules a timelocked upgrade to a wasm hash.
+    ScheduleUpgrade(BytesN<32>),
+    /// Cancels the scheduled upgrade.
+    CancelUpgrade,
+    /// Installs the scheduled upgrade once its delay has passed.
+    ExecuteUpgrade,
+    /// Migrates the stored data to the layout of the installed code.
+    Migrate,
+    Pause,
+    Unpause,
+    PauseCategory(PauseCategory),
+    UnpauseCategory(PauseCategory),
+    /// Replaces the signer set and the threshold.
+    SetSigners(Vec<Address>, u32),
+}
+
+/// A proposed [`Action`] and the signers who approved it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: Action,
+    /// Signers who approved the proposal, starting with the proposer.
+    pub approvals: Vec<Address>,
+    pub created_ledger: u32,
+    /// Last ledger at which the proposal can be approved or executed.
+    pub expiration_ledger: u32,
+    pub executed: bool,
+}
+
+/// Storage keys for the multisig.
+#[contracttype]
+pub enum MultisigStorageKey {
+    /// Current signer set.
+    Signers,
+    /// Number of distinct approvals needed to execute a proposal.
+    Threshold,
+    /// Maps a proposal id to its [`Proposal`].
+    Proposal(u32),
+    /// Number of proposals ever created, also the next proposal id.
+    ProposalCount,
+}
+
+/// Returns the current signer set, empty until the owner configures one.
+pub fn signers(e: &Env) -> Vec<Address> {
+    e.storage().instance().get(&MultisigStorageKey::Signers).unwrap_or_else(|| Vec::new(e))
+}
+
+/// Returns the number of approvals needed to execute a proposal, `0` until
+/// the owner configures the signers.
+pub fn threshold(e: &Env) -> u32 {
+    e.storage().instance().get(&MultisigStorageKey::Threshold).unwrap_or(0)
+}
+
+/// Returns true if `account` is part of the current signer set.
+pub fn is_signer(e: &Env, account: &Address) -> bool {
+    signers(e).contains(account)
+}
+
+/// Panics with [`ExampleContractError::MultisigRequired`] once the signers
+/// are configured, for single-key entry points whose action has to be
+/// proposed instead.
+pub fn ensure_single_key(e: &Env) {
+    if threshold(e) > 0 {
+        panic_with_error!(e, ExampleContractError::MultisigRequired);
+    }
+}
+
+/// Replaces the signer set and the threshold.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidSigners`] - When `signers` contains
+///   duplicates, or `threshold` is `0` or larger than the number of signers.
+///
+/// # Events
+///
+/// * topics - `["signers_updated"]`
+/// * data - `[signers: Vec<Address>, threshold: u32]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn set_signers(e: &Env, signers: &Vec<Address>, threshold: u32) {
+    if threshold == 0 || threshold > signers.len() {
+        panic_with_error!(e, ExampleContractError::InvalidSigners);
+    }
+    for (i, signer) in signers.iter().enumerate() {
+        if signers.first_index_of(&signer) != Some(i as u32) {
+            panic_with_error!(e, ExampleContractError::InvalidSigners);
+        }
+    }
+    e.storage().instance().set(&MultisigStorageKey::Signers, signers);
+    e.storage().instance().set(&MultisigStorageKey::Threshold, &threshold);
+
+    SignersUpdated { signers: signers.clone(), threshold }.publish(e);
+}
+
+/// Returns the number of proposals ever created.
+pub fn proposal_count(e: &Env) -> u32 {
+    e.storage().instance().get(&MultisigStorageKey::ProposalCount).unwrap_or(0)
+}
+
+/// Returns the proposal with id `id`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::ProposalNotFound`] - When no such proposal
+///   exists.
+pub fn read_proposal(e: &Env, id: u32) -> Proposal {
+    let key = MultisigStorageKey::Proposal(id);
+    let proposal = e
+        .storage()
+        .persistent()
+        .get::<_, Proposal>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::ProposalNotFound));
+    e.storage().persistent().extend_ttl(&key, PROPOSAL_TTL_THRESHOLD, PROPOSAL_EXTEND_AMOUNT);
+    proposal
+}
+
+fn write_proposal(e: &Env, proposal: &Proposal) {
+    let key = MultisigStorageKey::Proposal(proposal.id);
+    e.storage().persistent().set(&key, proposal);
+    e.storage().persistent().extend_ttl(&key, PROPOSAL_TTL_THRESHOLD, PROPOSAL_EXTEND_AMOUNT);
+}
+
+/// Creates a proposal for `action`, approved by `proposer`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::NotSigner`] - When `proposer` is not a signer.
+/// * [`ExampleContractError::InvalidDeadline`] - When `expiration_ledger` is
+///   in the past.
+///
+/// # Events
+///
+/// * topics - `["proposal_created", id: u32, proposer: Address]`
+/// * data - `[action: Action, expiration_ledger: u32]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn propose(e: &Env, proposer: &Address, action: &Action, expiration_ledger: u32) -> u32 {
+    ensure_signer(e, proposer);
+    if expiration_ledger < e.ledger().sequence() {
+        panic_with_error!(e, ExampleContractError::InvalidDeadline);
+    }
+
+    let id = proposal_count(e);
+    let proposal = Proposal {
+        id,
+        proposer: proposer.clone(),
+        action: action.clone(),
+        approvals: Vec::from_array(e, [proposer.clone()]),
+        created_ledger: e.ledger().sequence(),
+        expiration_ledger,
+        executed: false,
+    };
+    write_proposal(e, &proposal);
+    e.storage().instance().set(&MultisigStorageKey::ProposalCount, &(id + 1));
+
+    ProposalCreated { id, proposer: proposer.clone(), action: action.clone(), expiration_ledger }
+        .publish(e);
+    id
+}
+
+/// Adds the approval of `signer` to proposal `id`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::NotSigner`] - When `signer` is not a signer.
+/// * [`ExampleContractError::ProposalNotFound`] - When no such proposal
+///   exists.
+/// * [`ExampleContractError::ProposalExpired`] - When the proposal expired.
+/// * [`ExampleContractError::ProposalAlreadyExecuted`] - When the proposal
+///   was executed.
+/// * [`ExampleContractError::AlreadyApproved`] - When `signer` already
+///   approved the proposal.
+///
+/// # Events
+///
+/// * topics - `["proposal_approved", id: u32, signer: Address]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn approve(e: &Env, signer: &Address, id: u32) {
+    ensure_signer(e, signer);
+    let mut proposal = read_proposal(e, id);
+    ensure_pending(e, &proposal);
+    if proposal.approvals.contains(signer) {
+        panic_with_error!(e, ExampleContractError::AlreadyApproved);
+    }
+
+    proposal.approvals.push_back(signer.clone());
+    write_proposal(e, &proposal);
+
+    ProposalApproved { id, signer: signer.clone() }.publish(e);
+}
+
+/// Marks proposal `id` as executed and returns its action.
+///
+/// Only approvals from accounts that are still signers count towards the
+/// threshold.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::ProposalNotFound`] - When no such proposal
+///   exists.
+/// * [`ExampleContractError::ProposalExpired`] - When the proposal expired.
+/// * [`ExampleContractError::ProposalAlreadyExecuted`] - When the proposal
+///   was executed.
+/// * [`ExampleContractError::ThresholdNotReached`] - When fewer than
+///   `threshold` signers approved the proposal.
+///
+/// # Events
+///
+/// * topics - `["proposal_executed", id: u32]`
+/// * data - `[]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, carrying out the action is up to
+/// the caller.
+pub fn execute(e: &Env, id: u32) -> Action {
+    let mut proposal = read_proposal(e, id);
+    ensure_pending(e, &proposal);
+    let signers = signers(e);
+    let approvals = proposal.approvals.iter().filter(|a| signers.contains(a)).count() as u32;
+    if approvals < threshold(e) {
+        panic_with_error!(e, ExampleContractError::ThresholdNotReached);
+    }
+
+    proposal.executed = true;
+    write_proposal(e, &proposal);
+
+    ProposalExecuted { id }.publish(e);
+    proposal.action
+}
+
+/// Returns up to `limit` proposals in creation order, starting at id `start`.
+pub fn list_proposals(e: &Env, start: u32, limit: u32) -> Vec<Proposal> {
+    let mut proposals = Vec::new(e);
+    let end = start.saturating_add(limit).min(proposal_count(e));
+    for id in start..end {
+        proposals.push_back(read_proposal(e, id));
+    }
+    proposals
+}
+
+fn ensure_signer(e: &Env, account: &Address) {
+    if !is_signer(e, account) {
+        panic_with_error!(e, ExampleContractError::NotSigner);
+    }
+}
+
+fn ensure_pending(e: &Env, proposal: &Proposal) {
+    if proposal.executed {
+        panic_with_error!(e, ExampleContractError::ProposalAlreadyExecuted);
+    }
+    if e.ledger().sequence() > proposal.expiration_ledger {
+        panic_with_error!(e, ExampleContractError::ProposalExpired);
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the signer set or the threshold changes.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignersUpdated {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Event emitted when a signer proposes an action.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub proposer: Address,
+    pub action: Action,
+    pub expiration_ledger: u32,
+}
+
+/// Event emitted when a signer approves a proposal.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalApproved {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub signer: Address,
+}
+
+/// Event emitted when a proposal is executed.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    #[topic]
+    pub id: u32,
+}
@@ -0,0 +1,123 @@
+//! Pausing of individual operation categories.
+//!
+//! The global pause from the `Pausable` implementation stops everything at
+//! once. During an incident it is often enough to stop a single category, for
+//! example new escrow deposits, while releases and refunds keep draining the
+//! existing escrows. Categories are paused independently of each other and of
+//! the global pause, an operation runs only if neither applies.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Env, Vec};
+
+use crate::contract::ExampleContractError;
+
+/// Group of operations that can be paused together.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PauseCategory {
+    /// `transfer`, `transfer_from`, `batch_transfer` and `claim_vesting`.
+    Transfers,
+    /// Minting, directly or through a multisig proposal, and
+    /// `create_vesting`.
+    Mint,
+    /// `burn`, `burn_from` and `revoke_vesting`.
+    Burn,
+    /// Deposits into escrows and streams: `lock`, `lock_milestones`,
+    /// `lock_budget` and `create_stream`.
+    EscrowIn,
+    /// Payouts from escrows and streams: releases, milestone approvals,
+    /// dispute rulings, refunds, `withdraw_stream` and `cancel_stream`.
+    EscrowOut,
+}
+
+const CATEGORIES: [PauseCategory; 5] = [
+    PauseCategory::Transfers,
+    PauseCategory::Mint,
+    PauseCategory::Burn,
+    PauseCategory::EscrowIn,
+    PauseCategory::EscrowOut,
+];
+
+/// Storage keys for the paused categories.
+#[contracttype]
+pub enum PausingStorageKey {
+    /// Present while the category is paused.
+    Paused(PauseCategory),
+}
+
+/// Returns true if `category` is paused, regardless of the global pause.
+pub fn is_paused(e: &Env, category: PauseCategory) -> bool {
+    e.storage().instance().has(&PausingStorageKey::Paused(category))
+}
+
+/// Returns the categories that are currently paused.
+pub fn paused_categories(e: &Env) -> Vec<PauseCategory> {
+    let mut paused = Vec::new(e);
+    for category in CATEGORIES {
+        if is_paused(e, category) {
+            paused.push_back(category);
+        }
+    }
+    paused
+}
+
+/// Panics if `category` is paused.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::OperationPaused`] - When `category` is paused.
+pub fn when_not_paused(e: &Env, category: PauseCategory) {
+    if is_paused(e, category) {
+        panic_with_error!(e, ExampleContractError::OperationPaused);
+    }
+}
+
+/// Pauses `category`, pausing a paused category has no effect.
+///
+/// # Events
+///
+/// * topics - `["category_paused", category: PauseCategory]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn pause(e: &Env, category: PauseCategory) {
+    e.storage().instance().set(&PausingStorageKey::Paused(category), &true);
+
+    CategoryPaused { category }.publish(e);
+}
+
+/// Unpauses `category`, unpausing a category that is not paused has no
+/// effect.
+///
+/// # Events
+///
+/// * topics - `["category_unpaused", category: PauseCategory]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn unpause(e: &Env, category: PauseCategory) {
+    e.storage().instance().remove(&PausingStorageKey::Paused(category));
+
+    CategoryUnpaused { category }.publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when a category is paused.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryPaused {
+    #[topic]
+    pub category: PauseCategory,
+}
+
+/// Event emitted when a category is unpaused.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryUnpaused {
+    #[topic]
+    pub category: PauseCategory,
+}
@@ -0,0 +1,131 @@
+//! Gasless approvals signed off-chain.
+//!
+//! A holder without XLM for fees can sign a [`PermitMessage`] with the
+//! ed25519 key of their account, and anyone can submit it together with the
+//! signature to set the allowance on the holder's behalf. Every owner has a
+//! nonce that a permit must carry and that it consumes, so each signed message
+//! can be used once. The message also names the network and the token
+//! contract, so it cannot be replayed on another deployment.
+
+use soroban_sdk::{
+    contracttype, panic_with_error,
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env,
+};
+use stellar_tokens::fungible::{emit_approve, Base};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// The message an owner signs to approve `spender`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitMessage {
+    pub network_id: BytesN<32>,
+    pub contract: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    /// Last ledger at which the permit can be submitted, and at which the
+    /// allowance expires.
+    pub expiration_ledger: u32,
+    pub nonce: u32,
+}
+
+/// Storage keys for permits.
+#[contracttype]
+pub enum PermitStorageKey {
+    /// Maps an owner to the nonce its next permit must carry.
+    Nonce(Address),
+}
+
+/// Returns the nonce the next permit of `owner` must carry.
+pub fn nonce(e: &Env, owner: &Address) -> u32 {
+    let key = PermitStorageKey::Nonce(owner.clone());
+    let nonce = e.storage().persistent().get(&key);
+    if nonce.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    nonce.unwrap_or(0)
+}
+
+/// Returns the address of the Stellar account controlled by `public_key`.
+pub fn account_address(e: &Env, public_key: &BytesN<32>) -> Address {
+    // XDR of `ScVal::Address(ScAddress::Account(PublicKey::Ed25519(key)))`,
+    // which is valid for any 32 bytes.
+    let mut xdr = Bytes::from_array(e, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.append(&public_key.clone().into());
+    Address::from_xdr(e, &xdr).unwrap()
+}
+
+/// Builds the message the owner of `public_key` signs to let `spender` spend
+/// `amount` of their tokens until `expiration_ledger`.
+pub fn permit_message(
+    e: &Env,
+    public_key: &BytesN<32>,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u32,
+) -> PermitMessage {
+    PermitMessage {
+        network_id: e.ledger().network_id(),
+        contract: e.current_contract_address(),
+        owner: account_address(e, public_key),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+    }
+}
+
+/// Sets the allowance described by a [`PermitMessage`] once `signature` is
+/// verified against `public_key`, and consumes the owner's nonce.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::PermitExpired`] - When `expiration_ledger` has
+///   passed.
+/// * [`ExampleContractError::InvalidNonce`] - When `nonce` is not the
+///   owner's current nonce.
+/// * refer to [`Base::set_allowance`] errors.
+///
+/// The host traps when `signature` does not match the message.
+///
+/// # Events
+///
+/// * topics - `["approve", owner: Address, spender: Address]`
+/// * data - `[amount: i128, live_until_ledger: u32]`
+///
+/// # Notes
+///
+/// The signature is checked against the master key of the account only,
+/// regardless of the account's signers and thresholds.
+pub fn permit(
+    e: &Env,
+    public_key: &BytesN<32>,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u32,
+    signature: &BytesN<64>,
+) {
+    if e.ledger().sequence() > expiration_ledger {
+        panic_with_error!(e, ExampleContractError::PermitExpired);
+    }
+    let message = permit_message(e, public_key, spender, amount, expiration_ledger, nonce);
+    let owner = message.owner.clone();
+    if nonce != self::nonce(e, &owner) {
+        panic_with_error!(e, ExampleContractError::InvalidNonce);
+    }
+    e.crypto().ed25519_verify(public_key, &message.to_xdr(e), signature);
+
+    let key = PermitStorageKey::Nonce(owner.clone());
+    e.storage().persistent().set(&key, &(nonce + 1));
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+    Base::set_allowance(e, &owner, spender, amount, expiration_ledger);
+    emit_approve(e, &owner, spender, amount, expiration_ledger);
+}
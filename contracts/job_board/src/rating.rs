@@ -0,0 +1,166 @@
+//! Two-sided ratings left after a job is paid.
+//!
+//! Once an escrow is `Released`, its employer and its worker can each rate
+//! the other party once, with a score from [`MIN_SCORE`] to [`MAX_SCORE`]
+//! and the hash of a review stored off-chain. Only the two parties recorded
+//! on the escrow can rate, so every rating is backed by a paid job. Scores
+//! add up into a [`RatingSummary`] per rated account.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, BytesN, Env};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{self, Escrow, EscrowStatus, ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+pub const MIN_SCORE: u32 = 1;
+pub const MAX_SCORE: u32 = 5;
+
+/// Scale of the average returned by [`average_score`], e.g. `450` for 4.5.
+pub const AVERAGE_SCALE: u32 = 100;
+
+/// A rating one party of a job left for the other.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rating {
+    pub job_id: u64,
+    pub rater: Address,
+    pub ratee: Address,
+    pub score: u32,
+    /// Hash of the review text, which is stored off-chain.
+    pub review_hash: BytesN<32>,
+    pub ledger: u32,
+}
+
+/// Ratings received by an account.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RatingSummary {
+    pub count: u32,
+    /// Sum of the received scores.
+    pub total_score: u32,
+}
+
+/// Storage keys for ratings.
+#[contracttype]
+pub enum RatingStorageKey {
+    /// Maps a job id and a rater to the [`Rating`] they left.
+    Rating(u64, Address),
+    /// Maps an account to the [`RatingSummary`] of the ratings it received.
+    Summary(Address),
+}
+
+/// Returns the rating `rater` left for job `job_id`, if any.
+pub fn get_rating(e: &Env, job_id: u64, rater: &Address) -> Option<Rating> {
+    let key = RatingStorageKey::Rating(job_id, rater.clone());
+    let rating = e.storage().persistent().get::<_, Rating>(&key);
+    if rating.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    rating
+}
+
+/// Returns the summary of the ratings `account` received.
+pub fn summary(e: &Env, account: &Address) -> RatingSummary {
+    let key = RatingStorageKey::Summary(account.clone());
+    let summary = e.storage().persistent().get::<_, RatingSummary>(&key);
+    if summary.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    summary.unwrap_or_default()
+}
+
+/// Returns the average score `account` received in units of
+/// [`AVERAGE_SCALE`], `None` before its first rating.
+pub fn average_score(e: &Env, account: &Address) -> Option<u32> {
+    let summary = summary(e, account);
+    if summary.count == 0 {
+        return None;
+    }
+    Some(summary.total_score * AVERAGE_SCALE / summary.count)
+}
+
+/// Records the rating `rater` leaves for the other party of `escrow`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::EscrowNotReleased`] - When the escrow is not
+///   `Released`.
+/// * [`ExampleContractError::Unauthorized`] - When `rater` is neither the
+///   employer nor the worker of the escrow.
+/// * [`ExampleContractError::InvalidScore`] - When `score` is outside
+///   [`MIN_SCORE`]..=[`MAX_SCORE`].
+/// * [`ExampleContractError::AlreadyRated`] - When `rater` already rated
+///   this job.
+///
+/// # Events
+///
+/// * topics - `["job_rated", job_id: u64, rater: Address, ratee: Address]`
+/// * data - `[score: u32, review_hash: BytesN<32>]`
+///
+/// # Notes
+///
+/// Authorization for `rater` is expected to be checked by the caller.
+pub fn rate(e: &Env, rater: &Address, escrow: &Escrow, score: u32, review_hash: &BytesN<32>) {
+    if escrow.status != EscrowStatus::Released {
+        panic_with_error!(e, ExampleContractError::EscrowNotReleased);
+    }
+    let worker = escrow::worker(e, escrow);
+    let ratee = if *rater == escrow.employer {
+        worker
+    } else if *rater == worker {
+        escrow.employer.clone()
+    } else {
+        panic_with_error!(e, ExampleContractError::Unauthorized);
+    };
+    if !(MIN_SCORE..=MAX_SCORE).contains(&score) {
+        panic_with_error!(e, ExampleContractError::InvalidScore);
+    }
+    let key = RatingStorageKey::Rating(escrow.job_id, rater.clone());
+    if e.storage().persistent().has(&key) {
+        panic_with_error!(e, ExampleContractError::AlreadyRated);
+    }
+
+    let rating = Rating {
+        job_id: escrow.job_id,
+        rater: rater.clone(),
+        ratee: ratee.clone(),
+        score,
+        review_hash: review_hash.clone(),
+        ledger: e.ledger().sequence(),
+    };
+    e.storage().persistent().set(&key, &rating);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+    let mut summary = summary(e, &ratee);
+    summary.count += 1;
+    summary.total_score += score;
+    let summary_key = RatingStorageKey::Summary(ratee.clone());
+    e.storage().persistent().set(&summary_key, &summary);
+    e.storage().persistent().extend_ttl(&summary_key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+    JobRated {
+        job_id: escrow.job_id,
+        rater: rater.clone(),
+        ratee,
+        score,
+        review_hash: review_hash.clone(),
+    }
+    .publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when a party of a job rates the other.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobRated {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub rater: Address,
+    #[topic]
+    pub ratee: Address,
+    pub score: u32,
+    pub review_hash: BytesN<32>,
+}
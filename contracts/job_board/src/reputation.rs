@@ -0,0 +1,138 @@
+//! Per-account track record built from settled escrows.
+//!
+//! Every account has a [`Reputation`] that the contract updates as escrows
+//! are settled: a worker earns a completed job once an escrow is fully
+//! released to them, together with whatever they were paid, and the losing
+//! party of a dispute ruling gets a dispute on their record. A completed job
+//! counts as on time if the worker's last delivery came by the deadline, or,
+//! for a job released without any delivery on record, if it was released by
+//! the deadline.
+//!
+//! Reputation is bound to the account it was earned by. There is no way to
+//! transfer it, and only the contract itself writes it. Escrows never have
+//! the same account as employer and worker, so an account cannot earn
+//! reputation by paying itself.
+
+use soroban_sdk::{contractevent, contracttype, Address, Env};
+
+use crate::escrow::{Escrow, EscrowStatus, ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD};
+
+/// Denominator of the on-time rate returned by [`on_time_rate`].
+pub const RATE_DENOMINATOR: u32 = 10_000;
+
+/// Track record of an account.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reputation {
+    /// Escrows fully released to the account as worker.
+    pub jobs_completed: u32,
+    /// Part of `jobs_completed` delivered by the deadline.
+    pub jobs_on_time: u32,
+    /// Tokens paid to the account for work, after fees.
+    pub total_earned: i128,
+    /// Dispute rulings that went against the account.
+    pub disputes_lost: u32,
+}
+
+/// Storage keys for reputation records.
+#[contracttype]
+pub enum ReputationStorageKey {
+    /// Maps an account to its [`Reputation`].
+    Reputation(Address),
+    /// Maps the job id of an open escrow to the ledger of its latest
+    /// delivery, absent until the worker delivers.
+    LastDelivery(u64),
+}
+
+/// Returns the track record of `account`, all zeros for a newcomer.
+pub fn reputation(e: &Env, account: &Address) -> Reputation {
+    let key = ReputationStorageKey::Reputation(account.clone());
+    let reputation = e.storage().persistent().get::<_, Reputation>(&key);
+    if reputation.is_some() {
+        e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    }
+    reputation.unwrap_or_default()
+}
+
+/// Returns the share of the completed jobs of `account` that were delivered
+/// on time, in units of [`RATE_DENOMINATOR`], `None` before the first one.
+pub fn on_time_rate(e: &Env, account: &Address) -> Option<u32> {
+    let reputation = reputation(e, account);
+    if reputation.jobs_completed == 0 {
+        return None;
+    }
+    let rate = reputation.jobs_on_time as u64 * RATE_DENOMINATOR as u64
+        / reputation.jobs_completed as u64;
+    Some(rate as u32)
+}
+
+/// Notes a delivery on `escrow` at the current ledger, which makes the job
+/// late if the deadline has passed.
+pub fn record_delivery(e: &Env, escrow: &Escrow) {
+    let key = ReputationStorageKey::LastDelivery(escrow.job_id);
+    e.storage().persistent().set(&key, &e.ledger().sequence());
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+}
+
+/// Drops the delivery record of `escrow`, for escrows settled without
+/// completing a job.
+pub fn clear_delivery(e: &Env, escrow: &Escrow) {
+    e.storage().persistent().remove(&ReputationStorageKey::LastDelivery(escrow.job_id));
+}
+
+/// Credits `worker` with `amount` paid out of `escrow`, and with a completed
+/// job once the escrow is `Released`.
+///
+/// # Events
+///
+/// * topics - `["reputation_updated", account: Address]`
+/// * data - `[reputation: Reputation]`
+pub fn record_payment(e: &Env, escrow: &Escrow, worker: &Address, amount: i128) {
+    let mut reputation = reputation(e, worker);
+    reputation.total_earned += amount;
+    if escrow.status == EscrowStatus::Released {
+        reputation.jobs_completed += 1;
+        // Without a delivery on record, the release shows when the work was
+        // done at the latest.
+        let key = ReputationStorageKey::LastDelivery(escrow.job_id);
+        let delivered_ledger = e.storage().persistent().get::<_, u32>(&key);
+        if delivered_ledger.is_some() {
+            e.storage().persistent().remove(&key);
+        }
+        if delivered_ledger.unwrap_or(e.ledger().sequence()) <= escrow.deadline_ledger {
+            reputation.jobs_on_time += 1;
+        }
+    }
+    write_reputation(e, worker, &reputation);
+}
+
+/// Adds a lost dispute to the record of `account`.
+///
+/// # Events
+///
+/// * topics - `["reputation_updated", account: Address]`
+/// * data - `[reputation: Reputation]`
+pub fn record_dispute_lost(e: &Env, account: &Address) {
+    let mut reputation = reputation(e, account);
+    reputation.disputes_lost += 1;
+    write_reputation(e, account, &reputation);
+}
+
+fn write_reputation(e: &Env, account: &Address, reputation: &Reputation) {
+    let key = ReputationStorageKey::Reputation(account.clone());
+    e.storage().persistent().set(&key, reputation);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+
+    ReputationUpdated { account: account.clone(), reputation: reputation.clone() }.publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the track record of an account changes.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationUpdated {
+    #[topic]
+    pub account: Address,
+    pub reputation: Reputation,
+}
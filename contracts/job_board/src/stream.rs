@@ -0,0 +1,332 @@
+//! Payment streams for hourly and retainer work.
+//!
+//! An employer deposits a budget into a [`Stream`] that pays its worker a
+//! fixed amount per ledger, starting at the ledger the stream is created.
+//! What has accrued is tracked as a checkpoint, `accrued` as of
+//! `checkpoint_ledger`, which is moved forward whenever the stream changes,
+//! so pausing simply stops the clock. Accrual stops once the whole deposit
+//! has accrued.
+//!
+//! The worker can withdraw what has accrued at any time. The employer can
+//! pause and resume the stream, or cancel it, which pays the worker what has
+//! accrued so far and refunds the rest of the deposit. If the worker cannot be
+//! paid at that point, e.g. because their account is frozen, what accrued
+//! stays in the stream for them to withdraw later. Payouts to the worker are
+//! reduced by the platform fee, like releases from an escrow.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// Lifecycle of a stream.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StreamStatus {
+    /// Accruing to the worker.
+    Active,
+    /// Paused by the employer, nothing accrues.
+    Paused,
+    /// Cancelled by the employer and settled.
+    Cancelled,
+}
+
+/// A budget paid out to a worker at a fixed rate per ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub id: u32,
+    pub employer: Address,
+    pub worker: Address,
+    pub deposit: i128,
+    pub rate_per_ledger: i128,
+    /// Amount accrued to the worker as of `checkpoint_ledger`.
+    pub accrued: i128,
+    pub checkpoint_ledger: u32,
+    /// Part of the accrued amount already paid out to the worker.
+    pub withdrawn: i128,
+    pub status: StreamStatus,
+}
+
+/// Storage keys for the streams.
+#[contracttype]
+pub enum StreamStorageKey {
+    /// Maps a stream id to its [`Stream`].
+    Stream(u32),
+    /// Number of streams ever created, also the next stream id.
+    StreamCount,
+}
+
+/// Returns the stream with id `id`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::StreamNotFound`] - When no such stream exists.
+pub fn read_stream(e: &Env, id: u32) -> Stream {
+    let key = StreamStorageKey::Stream(id);
+    let stream = e
+        .storage()
+        .persistent()
+        .get::<_, Stream>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::StreamNotFound));
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    stream
+}
+
+/// Stores `stream` under its id.
+pub fn write_stream(e: &Env, stream: &Stream) {
+    let key = StreamStorageKey::Stream(stream.id);
+    e.storage().persistent().set(&key, stream);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+}
+
+/// Returns the number of streams ever created.
+pub fn stream_count(e: &Env) -> u32 {
+    e.storage().instance().get(&StreamStorageKey::StreamCount).unwrap_or(0)
+}
+
+/// Returns the amount accrued to the worker of `stream` at the current
+/// ledger, withdrawn or not.
+pub fn accrued(e: &Env, stream: &Stream) -> i128 {
+    if stream.status != StreamStatus::Active {
+        return stream.accrued;
+    }
+    let elapsed = (e.ledger().sequence() - stream.checkpoint_ledger) as i128;
+    let accrued = stream.accrued.saturating_add(stream.rate_per_ledger.saturating_mul(elapsed));
+    accrued.min(stream.deposit)
+}
+
+/// Returns the amount the worker of `stream` can withdraw right now.
+pub fn withdrawable(e: &Env, stream: &Stream) -> i128 {
+    accrued(e, stream) - stream.withdrawn
+}
+
+/// Creates a stream paying `deposit` to `worker` at `rate_per_ledger`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `deposit` or
+///   `rate_per_ledger` is not positive.
+///
+/// # Events
+///
+/// * topics - `["stream_created", id: u32, employer: Address, worker:
+///   Address]`
+/// * data - `[deposit: i128, rate_per_ledger: i128]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, moving the deposit into the
+/// contract is up to the caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn create_stream(
+    e: &Env,
+    employer: &Address,
+    worker: &Address,
+    deposit: i128,
+    rate_per_ledger: i128,
+) -> u32 {
+    if deposit <= 0 || rate_per_ledger <= 0 {
+        panic_with_error!(e, ExampleContractError::InvalidAmount);
+    }
+
+    let id = stream_count(e);
+    write_stream(
+        e,
+        &Stream {
+            id,
+            employer: employer.clone(),
+            worker: worker.clone(),
+            deposit,
+            rate_per_ledger,
+            accrued: 0,
+            checkpoint_ledger: e.ledger().sequence(),
+            withdrawn: 0,
+            status: StreamStatus::Active,
+        },
+    );
+    e.storage().instance().set(&StreamStorageKey::StreamCount, &(id + 1));
+
+    StreamCreated {
+        id,
+        employer: employer.clone(),
+        worker: worker.clone(),
+        deposit,
+        rate_per_ledger,
+    }
+    .publish(e);
+    id
+}
+
+/// Marks everything `stream` accrued but not yet withdrawn as withdrawn and
+/// returns that amount.
+///
+/// # Events
+///
+/// * topics - `["stream_withdrawn", id: u32, worker: Address]`
+/// * data - `[amount: i128]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, paying the worker is up to the
+/// caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn withdraw(e: &Env, stream: &mut Stream) -> i128 {
+    let amount = withdrawable(e, stream);
+    stream.withdrawn += amount;
+
+    StreamWithdrawn { id: stream.id, worker: stream.worker.clone(), amount }.publish(e);
+    amount
+}
+
+/// Stops `stream` from accruing.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::StreamNotActive`] - When the stream is paused
+///   or cancelled.
+///
+/// # Events
+///
+/// * topics - `["stream_paused", id: u32]`
+/// * data - `[accrued: i128]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn pause(e: &Env, stream: &mut Stream) {
+    if stream.status != StreamStatus::Active {
+        panic_with_error!(e, ExampleContractError::StreamNotActive);
+    }
+    checkpoint(e, stream);
+    stream.status = StreamStatus::Paused;
+
+    StreamPaused { id: stream.id, accrued: stream.accrued }.publish(e);
+}
+
+/// Lets a paused `stream` accrue again from the current ledger.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::StreamNotPaused`] - When the stream is not
+///   paused.
+///
+/// # Events
+///
+/// * topics - `["stream_resumed", id: u32]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn resume(e: &Env, stream: &mut Stream) {
+    if stream.status != StreamStatus::Paused {
+        panic_with_error!(e, ExampleContractError::StreamNotPaused);
+    }
+    stream.checkpoint_ledger = e.ledger().sequence();
+    stream.status = StreamStatus::Active;
+
+    StreamResumed { id: stream.id }.publish(e);
+}
+
+/// Stops `stream` for good and returns the amounts owed to the worker and
+/// refunded to the employer. The amount owed to the worker stays withdrawable
+/// until it is paid out.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::StreamNotActive`] - When the stream was already
+///   cancelled.
+///
+/// # Events
+///
+/// * topics - `["stream_cancelled", id: u32]`
+/// * data - `[worker_amount: i128, refund: i128]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, moving the funds is up to the
+/// caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn cancel(e: &Env, stream: &mut Stream) -> (i128, i128) {
+    if stream.status == StreamStatus::Cancelled {
+        panic_with_error!(e, ExampleContractError::StreamNotActive);
+    }
+    checkpoint(e, stream);
+    let worker_amount = stream.accrued - stream.withdrawn;
+    let refund = stream.deposit - stream.accrued;
+    stream.status = StreamStatus::Cancelled;
+
+    StreamCancelled { id: stream.id, worker_amount, refund }.publish(e);
+    (worker_amount, refund)
+}
+
+fn checkpoint(e: &Env, stream: &mut Stream) {
+    stream.accrued = accrued(e, stream);
+    stream.checkpoint_ledger = e.ledger().sequence();
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when an employer starts a stream.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCreated {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub employer: Address,
+    #[topic]
+    pub worker: Address,
+    pub deposit: i128,
+    pub rate_per_ledger: i128,
+}
+
+/// Event emitted when a worker withdraws from a stream.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamWithdrawn {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub worker: Address,
+    pub amount: i128,
+}
+
+/// Event emitted when an employer pauses a stream.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamPaused {
+    #[topic]
+    pub id: u32,
+    pub accrued: i128,
+}
+
+/// Event emitted when an employer resumes a stream.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamResumed {
+    #[topic]
+    pub id: u32,
+}
+
+/// Event emitted when an employer cancels a stream.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCancelled {
+    #[topic]
+    pub id: u32,
+    pub worker_amount: i128,
+    pub refund: i128,
+}
@@ -0,0 +1,143 @@
+//! Limits on token minting.
+//!
+//! The maximum total supply is fixed at construction. On top of it, the owner
+//! can configure a mint budget: at most `amount` tokens can be minted per
+//! epoch of `epoch_ledgers` ledgers, so a leaked minter key can only inflate
+//! the supply by a bounded amount before it is revoked. An epoch starts with
+//! the first mint after the previous one ended.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Env};
+use stellar_tokens::fungible::{capped, Base};
+
+use crate::contract::ExampleContractError;
+
+/// Maximum amount of tokens that can be minted per epoch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintBudget {
+    pub amount: i128,
+    pub epoch_ledgers: u32,
+}
+
+/// Tokens minted during the current epoch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintEpoch {
+    pub start_ledger: u32,
+    pub minted: i128,
+}
+
+/// Storage keys for the minting limits, the supply cap is stored by
+/// [`capped`].
+#[contracttype]
+pub enum SupplyStorageKey {
+    /// Maps to the [`MintBudget`], unset while minting is only capped by the
+    /// maximum supply.
+    Budget,
+    /// Maps to the current [`MintEpoch`].
+    Epoch,
+}
+
+/// Sets the maximum total supply.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `max_supply` is negative.
+pub fn set_max_supply(e: &Env, max_supply: i128) {
+    if max_supply < 0 {
+        panic_with_error!(e, ExampleContractError::InvalidAmount);
+    }
+    capped::set_cap(e, max_supply);
+}
+
+/// Returns the maximum total supply.
+pub fn max_supply(e: &Env) -> i128 {
+    capped::query_cap(e)
+}
+
+/// Returns the mint budget, if one has been configured.
+pub fn mint_budget(e: &Env) -> Option<MintBudget> {
+    e.storage().instance().get(&SupplyStorageKey::Budget)
+}
+
+/// Sets the mint budget, or removes it when `budget` is `None`. The current
+/// epoch starts over.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When the budget amount is
+///   negative or the epoch is empty.
+///
+/// # Events
+///
+/// * topics - `["mint_budget_updated"]`
+/// * data - `[budget: Option<MintBudget>]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn set_mint_budget(e: &Env, budget: &Option<MintBudget>) {
+    match budget {
+        Some(b) if b.amount < 0 || b.epoch_ledgers == 0 => {
+            panic_with_error!(e, ExampleContractError::InvalidAmount);
+        }
+        Some(b) => e.storage().instance().set(&SupplyStorageKey::Budget, b),
+        None => e.storage().instance().remove(&SupplyStorageKey::Budget),
+    }
+    e.storage().instance().remove(&SupplyStorageKey::Epoch);
+
+    MintBudgetUpdated { budget: budget.clone() }.publish(e);
+}
+
+/// Returns how much can still be minted in the current epoch, `None` when no
+/// budget is configured.
+pub fn remaining_mint_budget(e: &Env) -> Option<i128> {
+    let budget = mint_budget(e)?;
+    Some(budget.amount - current_epoch(e, &budget).minted)
+}
+
+/// Accounts for minting `amount` tokens against the supply cap and the mint
+/// budget.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::SupplyCapExceeded`] - When the total supply
+///   would exceed the maximum supply.
+/// * [`ExampleContractError::MintBudgetExceeded`] - When the amount minted in
+///   the current epoch would exceed the mint budget.
+///
+/// # Notes
+///
+/// This function only enforces the limits, minting is up to the caller.
+pub fn record_mint(e: &Env, amount: i128) {
+    let total_supply = Base::total_supply(e);
+    if total_supply.checked_add(amount).is_none_or(|supply| supply > max_supply(e)) {
+        panic_with_error!(e, ExampleContractError::SupplyCapExceeded);
+    }
+
+    if let Some(budget) = mint_budget(e) {
+        let mut epoch = current_epoch(e, &budget);
+        epoch.minted += amount;
+        if epoch.minted > budget.amount {
+            panic_with_error!(e, ExampleContractError::MintBudgetExceeded);
+        }
+        e.storage().instance().set(&SupplyStorageKey::Epoch, &epoch);
+    }
+}
+
+fn current_epoch(e: &Env, budget: &MintBudget) -> MintEpoch {
+    let ledger = e.ledger().sequence();
+    match e.storage().instance().get::<_, MintEpoch>(&SupplyStorageKey::Epoch) {
+        Some(epoch) if ledger < epoch.start_ledger.saturating_add(budget.epoch_ledgers) => epoch,
+        _ => MintEpoch { start_ledger: ledger, minted: 0 },
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the owner changes the mint budget.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintBudgetUpdated {
+    pub budget: Option<MintBudget>,
+}
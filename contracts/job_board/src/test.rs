@@ -1,11 +1,56 @@
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{
+        storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation,
+        Events as _, Ledger, MockAuth, MockAuthInvoke,
+    },
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, Event, IntoVal, String, Symbol, Val, Vec,
+};
 
-use crate::contract::{ExampleContract, ExampleContractClient};
+use crate::{
+    contract::{
+        ExampleContract, ExampleContractClient, ARBITER_ROLE, MAX_BATCH_SIZE, MINTER_ROLE,
+        UPGRADER_ROLE,
+    },
+    dispute::DisputeResolved,
+    escrow::{
+        EscrowLocked, EscrowRefunded, EscrowReleased, EscrowStatus, EscrowStorageKey,
+        MilestoneReleased, MilestoneStatus, DAY_IN_LEDGERS, ESCROW_EXTEND_AMOUNT,
+        FIRST_BUDGET_JOB_ID,
+    },
+    fee::{FeeTaken, StreamFeeTaken},
+    multisig::Action,
+    pausing::PauseCategory,
+    permit::PermitMessage,
+    rating::RatingSummary,
+    reputation::{Reputation, ReputationStorageKey},
+    stream::StreamStatus,
+    supply::MintBudget,
+    upgrade::UPGRADE_DELAY,
+};
+
+const MAX_SUPPLY: i128 = 1_000_000;
+
+/// Per-transaction limits of the current network settings.
+const TX_MAX_INSTRUCTIONS: i64 = 100_000_000;
+const TX_MAX_WRITE_ENTRIES: u32 = 50;
+
+/// Builds of the current code, rebuilt with `make testdata` by every change to
+/// the storage layout.
+const V1_WASM: &[u8] = include_bytes!("../testdata/job_board_v1.wasm");
+const V2_WASM: &[u8] = include_bytes!("../testdata/job_board_v2.wasm");
+
+fn assert_emitted(e: &Env, contract: &Address, event: impl Event) {
+    let emitted = (contract.clone(), event.topics(e), event.data(e));
+    assert!(e.events().all().contains(emitted), "event was not emitted");
+}
 
 fn create_client<'a>(e: &Env, owner: &Address, initial_supply: i128) -> ExampleContractClient<'a> {
-    let address = e.register(ExampleContract, (owner, initial_supply));
+    let address = e.register(ExampleContract, (owner, initial_supply, MAX_SUPPLY));
     ExampleContractClient::new(e, &address)
 }
 
@@ -20,6 +65,7 @@ fn initial_state() {
     assert_eq!(client.symbol(), String::from_str(&e, "JBT"));
     assert_eq!(client.name(), String::from_str(&e, "JobBoardToken"));
     assert_eq!(client.decimals(), 18);
+    assert_eq!(client.max_supply(), MAX_SUPPLY);
     assert!(!client.paused());
 }
 
@@ -85,7 +131,7 @@ fn mint_works() {
     let client = create_client(&e, &owner, 1000);
 
     e.mock_all_auths();
-    client.mint(&owner, &500);
+    client.mint(&owner, &500, &owner);
     assert_eq!(client.total_supply(), 1500);
     assert_eq!(client.balance(&owner), 1500);
 }
@@ -99,7 +145,7 @@ fn mint_fails_when_paused() {
 
     e.mock_all_auths();
     client.pause(&owner);
-    client.mint(&owner, &500);
+    client.mint(&owner, &500, &owner);
 }
 
 #[test]
@@ -125,3 +171,2646 @@ fn burn_fails_when_paused() {
     client.pause(&owner);
     client.burn(&owner, &200);
 }
+
+#[test]
+fn lock_creates_escrow() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    assert_eq!(client.balance(&owner), 700);
+    assert_eq!(client.balance(&client.address), 300);
+
+    let escrow = client.get_escrow(&7);
+    assert_eq!(escrow.job_id, 7);
+    assert_eq!(escrow.employer, owner);
+    assert_eq!(escrow.worker, Some(worker));
+    assert_eq!(escrow.amount, 300);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.created_ledger, e.ledger().sequence());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn lock_fails_for_existing_job() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    client.lock(&owner, &7, &worker, &100, &1000);
+}
+
+#[test]
+fn release_pays_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    client.release(&7, &owner);
+    assert_eq!(client.balance(&worker), 300);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.get_escrow(&7).status, EscrowStatus::Released);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn release_fails_when_already_released() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    client.release(&7, &owner);
+    client.release(&7, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn release_fails_for_unknown_job() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.release(&7, &owner);
+}
+
+#[test]
+fn list_escrows_pages_in_creation_order() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &30, &worker, &100, &1000);
+    client.lock(&owner, &10, &worker, &200, &1000);
+    client.lock(&owner, &20, &worker, &300, &1000);
+    assert_eq!(client.escrow_count(), 3);
+
+    let page = client.list_escrows(&1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).job_id, 10);
+    assert_eq!(page.get_unchecked(1).job_id, 20);
+}
+
+#[test]
+fn list_escrows_extends_index_ttl() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &30, &worker, &100, &(10 * DAY_IN_LEDGERS));
+    e.ledger().set_sequence_number(2 * DAY_IN_LEDGERS);
+    client.list_escrows(&0, &1);
+
+    let ttl = e.as_contract(&client.address, || {
+        e.storage().persistent().get_ttl(&EscrowStorageKey::Index(0))
+    });
+    assert_eq!(ttl, ESCROW_EXTEND_AMOUNT);
+}
+
+#[test]
+fn lock_requires_employer_auth() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &employer,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "lock",
+                args: (&employer, 7_u64, &worker, 300_i128, 1000_u32).into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .lock(&employer, &7, &worker, &300, &1000);
+
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            employer.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&e, "lock"),
+                    (&employer, 7_u64, &worker, 300_i128, 1000_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.get_escrow(&7).employer, employer);
+    assert_eq!(client.balance(&client.address), 300);
+}
+
+#[test]
+fn lock_fails_without_auth() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    assert!(client.try_lock(&employer, &7, &worker, &300, &1000).is_err());
+    assert_eq!(client.balance(&employer), 1000);
+    assert_eq!(client.escrow_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn lock_fails_when_signed_by_another_account() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let attacker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "lock",
+                args: (&employer, 7_u64, &attacker, 300_i128, 1000_u32).into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .lock(&employer, &7, &attacker, &300, &1000);
+}
+
+#[test]
+fn milestones_release_one_tranche_at_a_time() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &350);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    assert_eq!(client.balance(&client.address), 350);
+    assert_eq!(client.get_escrow(&7).amount, 350);
+
+    client.deliver_milestone(&7, &1);
+    client.approve_milestone(&employer, &7, &1);
+    assert_eq!(client.balance(&worker), 250);
+
+    let escrow = client.get_escrow(&7);
+    assert_eq!(escrow.released, 250);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.milestones.get_unchecked(0).status, MilestoneStatus::Pending);
+    assert_eq!(escrow.milestones.get_unchecked(1).status, MilestoneStatus::Released);
+
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&owner, &7, &0);
+    assert_eq!(client.balance(&worker), 350);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.get_escrow(&7).status, EscrowStatus::Released);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn approve_milestone_fails_when_not_delivered() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    client.approve_milestone(&employer, &7, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn approve_milestone_fails_for_worker() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&worker, &7, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn deliver_milestone_fails_for_unknown_index() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    client.deliver_milestone(&7, &2);
+}
+
+#[test]
+fn release_pays_remaining_milestones() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&employer, &7, &0);
+    client.release(&7, &employer);
+    assert_eq!(client.balance(&worker), 350);
+
+    let escrow = client.get_escrow(&7);
+    assert_eq!(escrow.released, 350);
+    assert_eq!(escrow.milestones.get_unchecked(1).status, MilestoneStatus::Released);
+}
+
+#[test]
+fn dispute_splits_remaining_funds() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let arbiter = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &400);
+    client.grant_role(&owner, &arbiter, &ARBITER_ROLE);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 300], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&employer, &7, &0);
+
+    client.open_dispute(&worker, &7);
+    assert_eq!(client.get_escrow(&7).status, EscrowStatus::Disputed);
+    assert_eq!(client.get_dispute(&7).unwrap().opened_by, worker);
+
+    client.resolve_dispute(&arbiter, &7, &2_500);
+    assert_eq!(client.balance(&worker), 175);
+    assert_eq!(client.balance(&employer), 225);
+    assert_eq!(client.balance(&client.address), 0);
+
+    let dispute = client.get_dispute(&7).unwrap();
+    assert_eq!(dispute.arbiter, Some(arbiter));
+    assert_eq!(dispute.worker_bps, Some(2_500));
+    assert_eq!(client.get_escrow(&7).status, EscrowStatus::Resolved);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn release_fails_when_disputed() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&employer, &7);
+    client.release(&7, &employer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn open_dispute_fails_for_third_party() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let stranger = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&stranger, &7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn open_dispute_fails_without_worker() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    client.open_dispute(&employer, &job_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn resolve_dispute_fails_for_unregistered_arbiter() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let arbiter = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.grant_role(&employer, &arbiter, &ARBITER_ROLE);
+    client.revoke_role(&employer, &arbiter, &ARBITER_ROLE);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&worker, &7);
+    client.resolve_dispute(&arbiter, &7, &5_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn resolve_dispute_fails_when_not_disputed() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let arbiter = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.grant_role(&employer, &arbiter, &ARBITER_ROLE);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.resolve_dispute(&arbiter, &7, &5_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn resolve_dispute_fails_for_invalid_split() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let arbiter = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.grant_role(&employer, &arbiter, &ARBITER_ROLE);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&worker, &7);
+    client.resolve_dispute(&arbiter, &7, &10_001);
+}
+
+#[test]
+fn refund_returns_funds_after_grace_period() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &400);
+    client.set_grace_period(&50);
+    assert_eq!(client.grace_period(), 50);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 300], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&employer, &7, &0);
+
+    e.ledger().set_sequence_number(1051);
+    client.refund(&7);
+    assert_eq!(client.balance(&employer), 300);
+    assert_eq!(client.balance(&worker), 100);
+    assert_eq!(client.get_escrow(&7).status, EscrowStatus::Refunded);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn refund_fails_during_grace_period() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_grace_period(&50);
+    client.lock(&employer, &7, &worker, &300, &1000);
+
+    e.ledger().set_sequence_number(1050);
+    client.refund(&7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn refund_fails_when_delivery_is_pending() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_grace_period(&50);
+    client.lock(&employer, &7, &worker, &300, &1000);
+
+    e.ledger().set_sequence_number(1020);
+    client.submit_delivery(&7);
+
+    e.ledger().set_sequence_number(1051);
+    client.refund(&7);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn deliver_fails_after_grace_period() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_grace_period(&50);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 300], &1000);
+
+    e.ledger().set_sequence_number(1051);
+    client.deliver_milestone(&7, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn lock_fails_for_past_deadline() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(1000);
+    client.lock(&employer, &7, &worker, &300, &1000);
+}
+
+#[test]
+fn budget_escrow_can_be_refunded_before_deadline() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    assert_eq!(job_id, FIRST_BUDGET_JOB_ID);
+    assert_eq!(client.get_escrow(&job_id).worker, None);
+    assert_eq!(client.balance(&client.address), 300);
+
+    client.refund(&job_id);
+    assert_eq!(client.balance(&employer), 1000);
+    assert_eq!(client.get_escrow(&job_id).status, EscrowStatus::Refunded);
+}
+
+#[test]
+fn lock_budget_allocates_job_ids() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &0, &worker, &100, &1000);
+    assert_eq!(client.lock_budget(&employer, &None, &100, &1000), FIRST_BUDGET_JOB_ID);
+    assert_eq!(client.lock_budget(&employer, &None, &100, &1000), FIRST_BUDGET_JOB_ID + 1);
+    assert_eq!(client.escrow_count(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn lock_fails_for_reserved_job_id() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &FIRST_BUDGET_JOB_ID, &worker, &100, &1000);
+}
+
+#[test]
+fn managed_budget_is_refunded_by_manager() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let manager = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &Some(manager.clone()), &300, &1000);
+    assert_eq!(client.escrow_manager(&job_id), Some(manager.clone()));
+
+    client.refund(&job_id);
+    let auths = e.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, manager);
+    assert_eq!(client.balance(&employer), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn managed_budget_cannot_be_assigned_by_employer() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let manager = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &Some(manager.clone()), &300, &1000);
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &employer,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "assign_worker",
+                args: (job_id, &worker).into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .assign_worker(&job_id, &worker);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn release_fails_without_worker() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    client.release(&job_id, &employer);
+}
+
+#[test]
+fn assign_worker_binds_budget_escrow() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    client.assign_worker(&job_id, &worker);
+    assert_eq!(client.get_escrow(&job_id).worker, Some(worker.clone()));
+
+    client.release(&job_id, &employer);
+    assert_eq!(client.balance(&worker), 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn assign_worker_fails_when_already_assigned() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.assign_worker(&7, &employer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn lock_milestones_fails_when_employer_is_worker() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &employer, &vec![&e, 100, 200], &1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn assign_worker_fails_for_employer() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    client.assign_worker(&job_id, &employer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn assign_worker_fails_after_delivery_window() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_grace_period(&50);
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+
+    e.ledger().set_sequence_number(1051);
+    client.assign_worker(&job_id, &worker);
+}
+
+#[test]
+fn release_sends_fee_to_treasury() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&250, &treasury);
+    let config = client.fee_config().unwrap();
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.treasury, treasury);
+
+    client.lock(&employer, &7, &worker, &400, &1000);
+    client.release(&7, &employer);
+    assert_eq!(client.balance(&treasury), 10);
+    assert_eq!(client.balance(&worker), 390);
+    assert_eq!(client.balance(&client.address), 0);
+}
+
+#[test]
+fn milestone_release_sends_fee_to_treasury() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&1_000, &treasury);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 300], &1000);
+    client.deliver_milestone(&7, &1);
+    client.approve_milestone(&employer, &7, &1);
+    assert_eq!(client.balance(&treasury), 30);
+    assert_eq!(client.balance(&worker), 270);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn set_fee_config_fails_above_max() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&1_001, &treasury);
+}
+
+#[test]
+fn deployer_holds_every_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    assert_eq!(client.get_admin(), Some(owner.clone()));
+    for role in ["minter", "pauser", "arbiter", "upgrader", "freezer", "verifier"] {
+        let role = Symbol::new(&e, role);
+        assert!(client.has_role(&owner, &role).is_some());
+        assert_eq!(client.get_role_member_count(&role), 1);
+        assert_eq!(client.get_role_member(&role, &0), owner);
+    }
+}
+
+#[test]
+fn granted_minter_can_mint() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let minter = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.grant_role(&owner, &minter, &MINTER_ROLE);
+    client.mint(&minter, &500, &minter);
+    assert_eq!(client.balance(&minter), 500);
+    assert_eq!(client.get_role_member_count(&MINTER_ROLE), 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn mint_fails_without_minter_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let account = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.mint(&account, &500, &account);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn mint_fails_after_role_is_renounced() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.renounce_role(&owner, &MINTER_ROLE);
+    client.mint(&owner, &500, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn release_fails_without_arbiter_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.release(&7, &employer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn pause_fails_without_pauser_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let pauser = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.grant_role(&owner, &pauser, &Symbol::new(&e, "pauser"));
+    client.revoke_role(&owner, &pauser, &Symbol::new(&e, "pauser"));
+    client.pause(&pauser);
+}
+
+#[test]
+fn ownership_moves_after_acceptance() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let new_owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer_ownership(&new_owner, &100);
+    assert_eq!(client.owner(), Some(owner.clone()));
+
+    client.accept_ownership();
+    assert_eq!(client.owner(), Some(new_owner.clone()));
+
+    // The new owner takes over role management from the deployer.
+    client.revoke_role(&new_owner, &owner, &MINTER_ROLE);
+    assert!(client.has_role(&owner, &MINTER_ROLE).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2200)")]
+fn accept_ownership_fails_after_expiry() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let new_owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer_ownership(&new_owner, &100);
+    e.ledger().set_sequence_number(101);
+    client.accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2200)")]
+fn accept_ownership_fails_after_cancellation() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let new_owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer_ownership(&new_owner, &100);
+    client.transfer_ownership(&new_owner, &0);
+    client.accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn accept_ownership_fails_for_another_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let new_owner = Address::generate(&e);
+    let attacker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer_ownership(&new_owner, &100);
+
+    e.set_auths(&[]);
+    client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "accept_ownership",
+                args: ().into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .accept_ownership();
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn transfer_ownership_fails_without_owner_auth() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let attacker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "transfer_ownership",
+                args: (&attacker, 100u32).into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .transfer_ownership(&attacker, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2001)")]
+fn renounce_ownership_freezes_admin_settings() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.renounce_ownership();
+    assert_eq!(client.owner(), None);
+    client.set_grace_period(&10);
+}
+
+#[test]
+fn propose_upgrade_schedules_after_delay() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+    let wasm_hash = BytesN::from_array(&e, &[7; 32]);
+    e.ledger().set_sequence_number(100);
+
+    e.mock_all_auths();
+    let executable_ledger = client.propose_upgrade(&wasm_hash, &owner);
+    assert_eq!(executable_ledger, 100 + UPGRADE_DELAY);
+
+    let pending = client.pending_upgrade().unwrap();
+    assert_eq!(pending.wasm_hash, wasm_hash);
+    assert_eq!(pending.scheduled_ledger, 100);
+    assert_eq!(pending.executable_ledger, executable_ledger);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn execute_upgrade_fails_before_delay() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let executable_ledger = client.propose_upgrade(&BytesN::from_array(&e, &[7; 32]), &owner);
+    e.ledger().set_sequence_number(executable_ledger - 1);
+    client.execute_upgrade(&owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn propose_upgrade_fails_while_another_is_pending() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.propose_upgrade(&BytesN::from_array(&e, &[7; 32]), &owner);
+    client.propose_upgrade(&BytesN::from_array(&e, &[8; 32]), &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn execute_upgrade_fails_after_cancellation() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let executable_ledger = client.propose_upgrade(&BytesN::from_array(&e, &[7; 32]), &owner);
+    client.cancel_upgrade(&owner);
+    assert_eq!(client.pending_upgrade(), None);
+
+    e.ledger().set_sequence_number(executable_ledger);
+    client.execute_upgrade(&owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn propose_upgrade_fails_without_upgrader_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let attacker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.propose_upgrade(&BytesN::from_array(&e, &[7; 32]), &attacker);
+}
+
+#[test]
+fn upgrade_to_v2_keeps_balances_and_escrows() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let signer = Address::generate(&e);
+    // Running the wasm fixtures costs more than the default test budget.
+    e.cost_estimate().budget().reset_unlimited();
+    let address = e.register(V1_WASM, (&owner, 1000_i128, MAX_SUPPLY));
+    let client = ExampleContractClient::new(&e, &address);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &600);
+    client.lock(&employer, &7, &worker, &300, &(10 * UPGRADE_DELAY));
+    let escrow = client.get_escrow(&7);
+    let stream_id = client.create_stream(&employer, &worker, &300, &1);
+    let vesting_id = client.create_vesting(&worker, &100, &0, &0, &(10 * UPGRADE_DELAY));
+    client.set_mint_budget(&Some(MintBudget { epoch_ledgers: 100, amount: 500 }));
+    client.pause_category(&PauseCategory::Burn, &owner);
+    client.set_signers(&vec![&e, signer.clone()], &1);
+    client.freeze(&signer, &owner);
+    assert_eq!(client.version(), 1);
+
+    // Once signers are configured, upgrades go through their proposals.
+    let execute = |action: Action| {
+        let id = client.propose(&signer, &action, &(10 * UPGRADE_DELAY));
+        client.execute_proposal(&id);
+    };
+    let v2_hash = e.deployer().upload_contract_wasm(V2_WASM);
+    execute(Action::ScheduleUpgrade(v2_hash));
+    e.ledger().set_sequence_number(client.pending_upgrade().unwrap().executable_ledger);
+    execute(Action::ExecuteUpgrade);
+
+    assert_eq!(client.version(), 1);
+    execute(Action::Migrate);
+    assert_eq!(client.version(), 2);
+
+    assert_eq!(client.balance(&owner), 400);
+    assert_eq!(client.balance(&address), 700);
+    assert_eq!(client.get_escrow(&7), escrow);
+    assert_eq!(client.max_supply(), MAX_SUPPLY);
+    assert_eq!(client.mint_budget(), Some(MintBudget { epoch_ledgers: 100, amount: 500 }));
+    assert_eq!(client.signers(), vec![&e, signer.clone()]);
+    assert!(client.is_frozen(&signer));
+    assert_eq!(client.paused_categories(), vec![&e, PauseCategory::Burn]);
+    assert_eq!(client.get_stream(&stream_id).deposit, 300);
+    assert_eq!(client.get_vesting(&vesting_id).total, 100);
+
+    execute(Action::Release(7));
+    assert_eq!(client.balance(&worker), 300);
+    assert_eq!(client.reputation(&worker).jobs_completed, 1);
+    client.cancel_stream(&stream_id);
+    assert_eq!(client.balance(&worker), 600);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn migrate_fails_when_replayed() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    e.cost_estimate().budget().reset_unlimited();
+    let address = e.register(V1_WASM, (&owner, 1000_i128, MAX_SUPPLY));
+    let client = ExampleContractClient::new(&e, &address);
+
+    e.mock_all_auths();
+    let v2_hash = e.deployer().upload_contract_wasm(V2_WASM);
+    let executable_ledger = client.propose_upgrade(&v2_hash, &owner);
+    e.ledger().set_sequence_number(executable_ledger);
+    client.execute_upgrade(&owner);
+
+    client.migrate(&owner);
+    client.migrate(&owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn migrate_fails_on_fresh_deployment() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.migrate(&owner);
+}
+
+fn setup_signers(e: &Env, client: &ExampleContractClient, threshold: u32) -> std::vec::Vec<Address> {
+    let signers = [Address::generate(e), Address::generate(e), Address::generate(e)];
+    client.set_signers(&Vec::from_array(e, signers.clone()), &threshold);
+    signers.to_vec()
+}
+
+#[test]
+fn multisig_mint_executes_at_threshold() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let account = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    let id = client.propose(&signers[0], &Action::Mint(account.clone(), 500), &100);
+    client.approve_proposal(&signers[2], &id);
+    client.execute_proposal(&id);
+    assert_eq!(client.balance(&account), 500);
+
+    let proposal = client.get_proposal(&id);
+    assert!(proposal.executed);
+    assert_eq!(proposal.approvals, vec![&e, signers[0].clone(), signers[2].clone()]);
+    assert_eq!(client.proposal_count(), 1);
+    assert_eq!(client.list_proposals(&0, &10), vec![&e, proposal]);
+}
+
+#[test]
+fn multisig_releases_escrow_and_pauses() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    let signers = setup_signers(&e, &client, 2);
+
+    let release = client.propose(&signers[0], &Action::Release(7), &100);
+    let pause = client.propose(&signers[1], &Action::Pause, &100);
+    client.approve_proposal(&signers[1], &release);
+    client.approve_proposal(&signers[2], &pause);
+    client.execute_proposal(&release);
+    client.execute_proposal(&pause);
+
+    assert_eq!(client.balance(&worker), 300);
+    assert!(client.paused());
+}
+
+#[test]
+fn multisig_upgrades_and_migrates_without_upgrader() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    e.cost_estimate().budget().reset_unlimited();
+    let address = e.register(V1_WASM, (&owner, 1000_i128, MAX_SUPPLY));
+    let client = ExampleContractClient::new(&e, &address);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    client.revoke_role(&owner, &owner, &UPGRADER_ROLE);
+    let expiration = 10 * UPGRADE_DELAY;
+    let execute = |action: Action| {
+        let id = client.propose(&signers[0], &action, &expiration);
+        client.approve_proposal(&signers[1], &id);
+        client.execute_proposal(&id);
+    };
+
+    let v2_hash = e.deployer().upload_contract_wasm(V2_WASM);
+    execute(Action::ScheduleUpgrade(v2_hash));
+    e.ledger().set_sequence_number(client.pending_upgrade().unwrap().executable_ledger);
+    execute(Action::ExecuteUpgrade);
+    execute(Action::Migrate);
+    assert_eq!(client.version(), 2);
+}
+
+#[test]
+fn proposal_ids_do_not_share_escrow_count() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    let signers = setup_signers(&e, &client, 2);
+    assert_eq!(client.propose(&signers[0], &Action::Pause, &100), 0);
+
+    assert_eq!(client.escrow_count(), 1);
+    assert_eq!(client.list_escrows(&0, &10).len(), 1);
+}
+
+#[test]
+fn multisig_creates_vesting_and_pauses_categories() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let beneficiary = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 1);
+    let execute = |action: Action| {
+        let id = client.propose(&signers[0], &action, &100);
+        client.execute_proposal(&id);
+    };
+
+    execute(Action::CreateVesting(beneficiary.clone(), 400, 0, 0, 100));
+    assert_eq!(client.get_vesting(&0).beneficiary, beneficiary);
+    assert_eq!(client.balance(&client.address), 400);
+
+    execute(Action::PauseCategory(PauseCategory::Transfers));
+    assert_eq!(client.paused_categories(), vec![&e, PauseCategory::Transfers]);
+    execute(Action::UnpauseCategory(PauseCategory::Transfers));
+    assert!(client.paused_categories().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn mint_fails_once_signers_configured() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let minter = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    setup_signers(&e, &client, 2);
+    // Roles granted by the owner do not bypass the signers.
+    client.grant_role(&owner, &minter, &MINTER_ROLE);
+    client.mint(&minter, &500, &minter);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn release_fails_once_signers_configured() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    setup_signers(&e, &client, 2);
+    client.release(&7, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn pause_fails_once_signers_configured() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    setup_signers(&e, &client, 2);
+    client.pause(&owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #27)")]
+fn execute_proposal_fails_below_threshold() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    let id = client.propose(&signers[0], &Action::Mint(owner.clone(), 500), &100);
+    client.execute_proposal(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn approve_proposal_fails_for_repeated_signer() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    let id = client.propose(&signers[0], &Action::Mint(owner.clone(), 500), &100);
+    client.approve_proposal(&signers[0], &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn approve_proposal_fails_after_expiry() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    let id = client.propose(&signers[0], &Action::Mint(owner.clone(), 500), &100);
+    e.ledger().set_sequence_number(101);
+    client.approve_proposal(&signers[1], &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn execute_proposal_fails_when_replayed() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 1);
+    let id = client.propose(&signers[0], &Action::Mint(owner.clone(), 500), &100);
+    client.execute_proposal(&id);
+    client.execute_proposal(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn propose_fails_for_non_signer() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    setup_signers(&e, &client, 2);
+    client.propose(&owner, &Action::Mint(owner.clone(), 500), &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #27)")]
+fn removed_signer_approvals_do_not_count() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let signers = setup_signers(&e, &client, 2);
+    let mint = client.propose(&signers[0], &Action::Mint(owner.clone(), 500), &100);
+    client.approve_proposal(&signers[1], &mint);
+
+    let remaining = vec![&e, signers[1].clone(), signers[2].clone()];
+    let rotate = client.propose(&signers[1], &Action::SetSigners(remaining, 2), &100);
+    client.approve_proposal(&signers[2], &rotate);
+    client.execute_proposal(&rotate);
+
+    client.execute_proposal(&mint);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn set_signers_fails_for_unreachable_threshold() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    setup_signers(&e, &client, 4);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn set_signers_fails_once_configured() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    setup_signers(&e, &client, 2);
+    setup_signers(&e, &client, 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn mint_fails_above_max_supply() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.mint(&owner, &(MAX_SUPPLY - 1000), &owner);
+    assert_eq!(client.total_supply(), MAX_SUPPLY);
+    client.mint(&owner, &1, &owner);
+}
+
+#[test]
+fn mint_budget_resets_every_epoch() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+    let budget = MintBudget { amount: 500, epoch_ledgers: 100 };
+
+    e.mock_all_auths();
+    assert_eq!(client.remaining_mint_budget(), None);
+    client.set_mint_budget(&Some(budget.clone()));
+    assert_eq!(client.mint_budget(), Some(budget));
+
+    client.mint(&owner, &300, &owner);
+    client.mint(&owner, &200, &owner);
+    assert_eq!(client.remaining_mint_budget(), Some(0));
+
+    e.ledger().set_sequence_number(100);
+    assert_eq!(client.remaining_mint_budget(), Some(500));
+    client.mint(&owner, &500, &owner);
+    assert_eq!(client.balance(&owner), 2000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn mint_fails_above_epoch_budget() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.set_mint_budget(&Some(MintBudget { amount: 500, epoch_ledgers: 100 }));
+    client.mint(&owner, &300, &owner);
+    e.ledger().set_sequence_number(99);
+    client.mint(&owner, &201, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn multisig_mint_respects_epoch_budget() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.set_mint_budget(&Some(MintBudget { amount: 500, epoch_ledgers: 100 }));
+    let signers = setup_signers(&e, &client, 1);
+    let id = client.propose(&signers[0], &Action::Mint(owner.clone(), 501), &100);
+    client.execute_proposal(&id);
+}
+
+#[test]
+fn freeze_only_affects_frozen_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let spammer = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &spammer, &100);
+    client.freeze(&spammer, &owner);
+    assert!(client.is_frozen(&spammer));
+    assert!(!client.is_frozen(&user));
+
+    client.transfer(&owner, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+
+    client.unfreeze(&spammer, &owner);
+    assert!(!client.is_frozen(&spammer));
+    client.transfer(&spammer, &user, &100);
+    assert_eq!(client.balance(&user), 200);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn transfer_fails_to_frozen_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let spammer = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.freeze(&spammer, &owner);
+    client.transfer(&owner, &spammer, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn transfer_from_fails_for_frozen_owner() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let spammer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &spammer, &100);
+    client.approve(&spammer, &spender, &100, &1000);
+    client.freeze(&spammer, &owner);
+    client.transfer_from(&spender, &spammer, &spender, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn burn_fails_for_frozen_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.freeze(&owner, &owner);
+    client.burn(&owner, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn mint_fails_to_frozen_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let spammer = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.freeze(&spammer, &owner);
+    client.mint(&spammer, &100, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn multisig_mint_fails_to_frozen_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let spammer = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.freeze(&spammer, &owner);
+    let signers = setup_signers(&e, &client, 1);
+    let id = client.propose(&signers[0], &Action::Mint(spammer.clone(), 100), &100);
+    client.execute_proposal(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn lock_fails_for_frozen_employer() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.freeze(&employer, &owner);
+    client.lock(&employer, &7, &worker, &300, &1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn release_fails_for_frozen_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.freeze(&worker, &owner);
+    client.release(&7, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn freeze_fails_without_freezer_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.freeze(&owner, &user);
+}
+
+#[test]
+fn allowlist_gates_transfers_once_enabled() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    // Nothing is enforced until the mode is switched on.
+    client.transfer(&owner, &bob, &100);
+    assert!(!client.allowlist_enabled());
+
+    client.allow(&vec![&e, owner.clone(), alice.clone(), bob.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    assert!(client.allowlist_enabled());
+    assert!(client.is_allowed(&alice));
+
+    client.transfer(&owner, &alice, &100);
+    client.mint(&alice, &50, &owner);
+    assert_eq!(client.balance(&alice), 150);
+
+    client.disallow(&vec![&e, bob.clone()], &owner);
+    assert!(!client.is_allowed(&bob));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn transfer_fails_to_unlisted_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let outsider = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.allow(&vec![&e, owner.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    client.transfer(&owner, &outsider, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn transfer_from_fails_from_removed_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &holder, &100);
+    client.approve(&holder, &spender, &100, &1000);
+    client.allow(&vec![&e, owner.clone(), holder.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    client.disallow(&vec![&e, holder.clone()], &owner);
+    client.transfer_from(&spender, &holder, &owner, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn mint_fails_to_unlisted_account() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.set_allowlist_enabled(&true, &owner);
+    client.mint(&owner, &100, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn release_fails_for_unlisted_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.allow(&vec![&e, employer.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    client.release(&7, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn resolve_dispute_fails_for_unlisted_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&worker, &7);
+    client.allow(&vec![&e, employer.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    client.resolve_dispute(&owner, &7, &5_000);
+}
+
+#[test]
+fn resolve_dispute_refunds_employer_of_unlisted_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&worker, &7);
+    client.allow(&vec![&e, employer.clone()], &owner);
+    client.set_allowlist_enabled(&true, &owner);
+    client.resolve_dispute(&owner, &7, &0);
+    assert_eq!(client.balance(&employer), 300);
+    assert_eq!(client.balance(&worker), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn allow_fails_without_verifier_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.allow(&vec![&e, user.clone()], &user);
+}
+
+#[test]
+fn paused_escrow_in_still_drains_escrows() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &600);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    let budget_id = client.lock_budget(&employer, &None, &300, &1000);
+
+    client.pause_category(&PauseCategory::EscrowIn, &owner);
+    assert!(client.category_paused(&PauseCategory::EscrowIn));
+    assert!(!client.category_paused(&PauseCategory::EscrowOut));
+    assert!(!client.paused());
+
+    client.release(&7, &owner);
+    client.refund(&budget_id);
+    client.transfer(&employer, &worker, &100);
+    assert_eq!(client.balance(&worker), 400);
+    assert_eq!(client.balance(&employer), 200);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn lock_fails_when_escrow_in_paused() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.pause_category(&PauseCategory::EscrowIn, &owner);
+    client.lock(&employer, &7, &worker, &300, &1000);
+}
+
+#[test]
+fn paused_categories_lists_each_category() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.pause_category(&PauseCategory::Burn, &owner);
+    client.pause_category(&PauseCategory::Transfers, &owner);
+    assert_eq!(
+        client.paused_categories(),
+        vec![&e, PauseCategory::Transfers, PauseCategory::Burn]
+    );
+
+    client.unpause_category(&PauseCategory::Transfers, &owner);
+    assert_eq!(client.paused_categories(), vec![&e, PauseCategory::Burn]);
+    client.transfer(&owner, &owner, &1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn mint_fails_when_mint_paused() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.pause_category(&PauseCategory::Mint, &owner);
+    client.mint(&owner, &100, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn release_fails_when_escrow_out_paused() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.pause_category(&PauseCategory::EscrowOut, &owner);
+    client.release(&7, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn pause_category_fails_without_pauser_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.pause_category(&PauseCategory::Transfers, &user);
+}
+
+#[test]
+fn lock_emits_versioned_escrow_locked() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+
+    let event = EscrowLocked {
+        job_id: 7,
+        employer: employer.clone(),
+        worker: Some(worker),
+        amount: 300,
+        deadline_ledger: 1000,
+    };
+    let topics: Vec<Val> = vec![
+        &e,
+        Symbol::new(&e, "escrow_locked").into_val(&e),
+        Symbol::new(&e, "v1").into_val(&e),
+        7u64.into_val(&e),
+        employer.into_val(&e),
+    ];
+    assert_eq!(event.topics(&e), topics);
+    assert_emitted(&e, &client.address, event);
+}
+
+#[test]
+fn release_emits_escrow_released_and_fee_taken() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&500, &treasury);
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.release(&7, &owner);
+
+    assert_emitted(&e, &client.address, EscrowReleased { job_id: 7, worker, amount: 300, fee: 15 });
+    assert_emitted(&e, &client.address, FeeTaken { job_id: 7, treasury, amount: 15 });
+}
+
+#[test]
+fn approve_milestone_emits_milestone_released() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 200], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&employer, &7, &0);
+
+    let event =
+        MilestoneReleased { job_id: 7, worker: worker.clone(), index: 0, amount: 100, fee: 0 };
+    let topics: Vec<Val> = vec![
+        &e,
+        Symbol::new(&e, "milestone_released").into_val(&e),
+        Symbol::new(&e, "v1").into_val(&e),
+        7u64.into_val(&e),
+        worker.into_val(&e),
+    ];
+    assert_eq!(event.topics(&e), topics);
+    assert_emitted(&e, &client.address, event);
+}
+
+#[test]
+fn resolve_dispute_emits_dispute_resolved() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &7, &worker, &300, &1000);
+    client.open_dispute(&worker, &7);
+    client.resolve_dispute(&owner, &7, &5_000);
+
+    let event = DisputeResolved {
+        job_id: 7,
+        arbiter: owner.clone(),
+        worker_amount: 150,
+        employer_amount: 150,
+    };
+    let topics: Vec<Val> = vec![
+        &e,
+        Symbol::new(&e, "dispute_resolved").into_val(&e),
+        Symbol::new(&e, "v1").into_val(&e),
+        7u64.into_val(&e),
+        owner.into_val(&e),
+    ];
+    assert_eq!(event.topics(&e), topics);
+    assert_emitted(&e, &client.address, event);
+}
+
+#[test]
+fn refund_emits_escrow_refunded() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    let job_id = client.lock_budget(&employer, &None, &300, &1000);
+    client.refund(&job_id);
+
+    assert_emitted(&e, &client.address, EscrowRefunded { job_id, employer, amount: 300 });
+}
+
+#[test]
+fn batch_transfer_pays_every_recipient() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let transfers = vec![&e, (alice.clone(), 100), (bob.clone(), 200), (alice.clone(), 50)];
+    client.batch_transfer(&owner, &transfers);
+
+    // The whole batch needs a single authorization from the sender.
+    let auths = e.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, owner);
+
+    assert_eq!(client.balance(&alice), 150);
+    assert_eq!(client.balance(&bob), 200);
+    assert_eq!(client.balance(&owner), 650);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1000)")]
+fn batch_transfer_fails_when_paused() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.pause(&owner);
+    client.batch_transfer(&owner, &vec![&e, (Address::generate(&e), 100)]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn batch_transfer_fails_above_max_batch_size() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+    let mut transfers = Vec::new(&e);
+    for _ in 0..=MAX_BATCH_SIZE {
+        transfers.push_back((Address::generate(&e), 1_i128));
+    }
+
+    e.mock_all_auths();
+    client.batch_transfer(&owner, &transfers);
+}
+
+#[test]
+fn batch_release_pays_every_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &500);
+    client.lock(&employer, &1, &alice, &200, &1000);
+    client.lock(&employer, &2, &bob, &300, &1000);
+    client.batch_release(&vec![&e, (1, 200), (2, 300)], &owner);
+
+    assert_eq!(client.balance(&alice), 200);
+    assert_eq!(client.balance(&bob), 300);
+    assert_eq!(client.get_escrow(&2).status, EscrowStatus::Released);
+}
+
+#[test]
+fn batch_release_reverts_entirely_on_amount_mismatch() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &500);
+    client.lock(&employer, &1, &alice, &200, &1000);
+    client.lock(&employer, &2, &bob, &300, &1000);
+
+    let result = client.try_batch_release(&vec![&e, (1, 200), (2, 250)], &owner);
+    assert!(result.is_err());
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.get_escrow(&1).status, EscrowStatus::Locked);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn batch_release_fails_without_arbiter_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &300);
+    client.lock(&employer, &1, &worker, &300, &1000);
+    client.batch_release(&vec![&e, (1, 300)], &employer);
+}
+
+#[test]
+fn full_batch_transfer_fits_transaction_limits() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+    let mut transfers = Vec::new(&e);
+    for _ in 0..MAX_BATCH_SIZE {
+        transfers.push_back((Address::generate(&e), 1_i128));
+    }
+
+    e.mock_all_auths();
+    e.cost_estimate().budget().reset_default();
+    client.batch_transfer(&owner, &transfers);
+
+    let resources = e.cost_estimate().resources();
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS);
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES);
+}
+
+#[test]
+fn full_batch_release_fits_transaction_limits() {
+    assert_full_batch_release_fits(false);
+}
+
+#[test]
+fn full_batch_release_of_delivered_jobs_fits_transaction_limits() {
+    // Releasing a delivered job also clears its delivery record.
+    assert_full_batch_release_fits(true);
+}
+
+fn assert_full_batch_release_fits(delivered: bool) {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &owner, 10_000);
+    let mut releases = Vec::new(&e);
+
+    // With a non-zero fee every release also pays the treasury.
+    e.mock_all_auths();
+    client.set_fee_config(&100, &treasury);
+    for job_id in 0..MAX_BATCH_SIZE as u64 {
+        client.lock(&owner, &job_id, &Address::generate(&e), &100, &1000);
+        releases.push_back((job_id, 100_i128));
+    }
+    if delivered {
+        e.ledger().set_sequence_number(1001);
+        for job_id in 0..MAX_BATCH_SIZE as u64 {
+            client.submit_delivery(&job_id);
+        }
+    }
+    e.cost_estimate().budget().reset_default();
+    client.batch_release(&releases, &owner);
+
+    let resources = e.cost_estimate().resources();
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS);
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES);
+}
+
+fn keypair(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn account_of(e: &Env, key: &SigningKey) -> Address {
+    let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+    Address::from_str(e, &strkey)
+}
+
+fn sign_permit(
+    e: &Env,
+    client: &ExampleContractClient,
+    key: &SigningKey,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u32,
+) -> BytesN<64> {
+    let message = PermitMessage {
+        network_id: e.ledger().network_id(),
+        contract: client.address.clone(),
+        owner: account_of(e, key),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+    };
+    let xdr: std::vec::Vec<u8> = message.to_xdr(e).iter().collect();
+    BytesN::from_array(e, &key.sign(&xdr).to_bytes())
+}
+
+#[test]
+fn permit_sets_allowance_without_owner_auth() {
+    let e = Env::default();
+    let deployer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let key = keypair(1);
+    let owner = account_of(&e, &key);
+    let client = create_client(&e, &deployer, 1000);
+    e.mock_all_auths();
+    client.transfer(&deployer, &owner, &500);
+    e.set_auths(&[]);
+
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    let signature = sign_permit(&e, &client, &key, &spender, 300, 1000, 0);
+    client.permit(&public_key, &spender, &300, &1000, &0, &signature);
+    assert_eq!(client.allowance(&owner, &spender), 300);
+    assert_eq!(client.nonce(&owner), 1);
+
+    e.mock_all_auths();
+    client.transfer_from(&spender, &owner, &spender, &200);
+    assert_eq!(client.balance(&spender), 200);
+    assert_eq!(client.allowance(&owner, &spender), 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn permit_fails_when_replayed() {
+    let e = Env::default();
+    let deployer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let key = keypair(1);
+    let client = create_client(&e, &deployer, 1000);
+
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    let signature = sign_permit(&e, &client, &key, &spender, 300, 1000, 0);
+    client.permit(&public_key, &spender, &300, &1000, &0, &signature);
+    client.permit(&public_key, &spender, &300, &1000, &0, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #35)")]
+fn permit_fails_after_expiry() {
+    let e = Env::default();
+    let deployer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let key = keypair(1);
+    let client = create_client(&e, &deployer, 1000);
+
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    let signature = sign_permit(&e, &client, &key, &spender, 300, 1000, 0);
+    e.ledger().set_sequence_number(1001);
+    client.permit(&public_key, &spender, &300, &1000, &0, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn permit_fails_for_tampered_amount() {
+    let e = Env::default();
+    let deployer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let key = keypair(1);
+    let client = create_client(&e, &deployer, 1000);
+
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    let signature = sign_permit(&e, &client, &key, &spender, 300, 1000, 0);
+    client.permit(&public_key, &spender, &900, &1000, &0, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn permit_fails_when_signed_by_another_key() {
+    let e = Env::default();
+    let deployer = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let key = keypair(1);
+    let client = create_client(&e, &deployer, 1000);
+
+    let public_key = BytesN::from_array(&e, &key.verifying_key().to_bytes());
+    let signature = sign_permit(&e, &client, &keypair(2), &spender, 300, 1000, 0);
+    client.permit(&public_key, &spender, &300, &1000, &0, &signature);
+}
+
+#[test]
+fn vesting_unlocks_after_cliff_then_linearly() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let beneficiary = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+    let id = client.create_vesting(&beneficiary, &400, &100, &200, &500);
+    assert_eq!(client.total_supply(), 1400);
+    assert_eq!(client.balance(&client.address), 400);
+
+    e.ledger().set_sequence_number(199);
+    assert_eq!(client.vested_amount(&id), 0);
+    assert_eq!(client.claim_vesting(&id), 0);
+
+    e.ledger().set_sequence_number(200);
+    assert_eq!(client.claim_vesting(&id), 100);
+    assert_eq!(
+        e.auths()[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&e, "claim_vesting"),
+            (id,).into_val(&e),
+        ))
+    );
+    assert_eq!(e.auths()[0].0, beneficiary);
+
+    e.ledger().set_sequence_number(350);
+    assert_eq!(client.claim_vesting(&id), 150);
+
+    e.ledger().set_sequence_number(600);
+    assert_eq!(client.claim_vesting(&id), 150);
+    assert_eq!(client.balance(&beneficiary), 400);
+    assert_eq!(client.get_vesting(&id).claimed, 400);
+}
+
+#[test]
+fn revoke_vesting_burns_unvested_part() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let beneficiary = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_vesting(&beneficiary, &400, &0, &0, &400);
+    e.ledger().set_sequence_number(100);
+    assert_eq!(client.revoke_vesting(&id), 300);
+    assert_eq!(client.total_supply(), 1100);
+
+    // What vested before the revocation can still be claimed, nothing more.
+    e.ledger().set_sequence_number(400);
+    assert_eq!(client.vested_amount(&id), 100);
+    assert_eq!(client.claim_vesting(&id), 100);
+    assert_eq!(client.balance(&client.address), 0);
+}
+
+#[test]
+fn vesting_ids_do_not_share_escrow_count() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    assert_eq!(client.create_vesting(&worker, &400, &0, &0, &500), 0);
+
+    assert_eq!(client.vesting_count(), 1);
+    assert_eq!(client.escrow_count(), 1);
+    assert_eq!(client.list_escrows(&0, &10).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #37)")]
+fn create_vesting_fails_when_cliff_after_end() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.create_vesting(&Address::generate(&e), &400, &100, &600, &500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn create_vesting_fails_above_max_supply() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.create_vesting(&Address::generate(&e), &MAX_SUPPLY, &0, &0, &500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #38)")]
+fn revoke_vesting_fails_when_already_revoked() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_vesting(&Address::generate(&e), &400, &0, &0, &500);
+    client.revoke_vesting(&id);
+    client.revoke_vesting(&id);
+}
+
+#[test]
+fn stream_accrues_per_ledger_until_deposit_runs_out() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+    let id = client.create_stream(&employer, &worker, &500, &2);
+    assert_eq!(client.balance(&client.address), 500);
+
+    e.ledger().set_sequence_number(150);
+    assert_eq!(client.stream_withdrawable(&id), 100);
+    assert_eq!(client.withdraw_stream(&id), 100);
+    assert_eq!(e.auths()[0].0, worker);
+    assert_eq!(client.balance(&worker), 100);
+
+    e.ledger().set_sequence_number(1000);
+    assert_eq!(client.withdraw_stream(&id), 400);
+    assert_eq!(client.withdraw_stream(&id), 0);
+    assert_eq!(client.balance(&worker), 500);
+}
+
+#[test]
+fn stream_payouts_send_fee_to_treasury() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&250, &treasury);
+    let id = client.create_stream(&employer, &worker, &800, &2);
+
+    e.ledger().set_sequence_number(200);
+    assert_eq!(client.withdraw_stream(&id), 400);
+    assert_emitted(
+        &e,
+        &client.address,
+        StreamFeeTaken { stream_id: id, treasury: treasury.clone(), amount: 10 },
+    );
+    assert_eq!(client.balance(&worker), 390);
+
+    e.ledger().set_sequence_number(300);
+    client.cancel_stream(&id);
+    assert_eq!(client.balance(&worker), 585);
+    assert_eq!(client.balance(&treasury), 15);
+    assert_eq!(client.balance(&employer), 400);
+    assert_eq!(client.balance(&client.address), 0);
+}
+
+#[test]
+fn stream_ids_do_not_share_escrow_count() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.lock(&owner, &7, &worker, &300, &1000);
+    assert_eq!(client.create_stream(&owner, &worker, &300, &1), 0);
+
+    assert_eq!(client.stream_count(), 1);
+    assert_eq!(client.escrow_count(), 1);
+    assert_eq!(client.list_escrows(&0, &10).len(), 1);
+}
+
+#[test]
+fn paused_stream_stops_accruing() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_stream(&employer, &worker, &500, &2);
+    e.ledger().set_sequence_number(50);
+    client.pause_stream(&id);
+    assert_eq!(client.get_stream(&id).status, StreamStatus::Paused);
+
+    e.ledger().set_sequence_number(150);
+    assert_eq!(client.stream_withdrawable(&id), 100);
+
+    client.resume_stream(&id);
+    e.ledger().set_sequence_number(160);
+    assert_eq!(client.stream_withdrawable(&id), 120);
+}
+
+#[test]
+fn cancel_stream_splits_deposit_pro_rata() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &500);
+    let id = client.create_stream(&employer, &worker, &500, &2);
+    e.ledger().set_sequence_number(50);
+    client.withdraw_stream(&id);
+    e.ledger().set_sequence_number(100);
+    client.cancel_stream(&id);
+
+    assert_eq!(client.balance(&worker), 200);
+    assert_eq!(client.balance(&employer), 300);
+    assert_eq!(client.balance(&client.address), 0);
+    assert_eq!(client.get_stream(&id).status, StreamStatus::Cancelled);
+
+    e.ledger().set_sequence_number(200);
+    assert_eq!(client.stream_withdrawable(&id), 0);
+}
+
+#[test]
+fn cancel_stream_refunds_employer_of_frozen_worker() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    client.transfer(&owner, &employer, &500);
+    let id = client.create_stream(&employer, &worker, &500, &2);
+    e.ledger().set_sequence_number(100);
+    client.freeze(&worker, &owner);
+    client.cancel_stream(&id);
+
+    // The accrued part stays in the stream until the worker is unfrozen.
+    assert_eq!(client.balance(&employer), 300);
+    assert_eq!(client.balance(&worker), 0);
+    assert_eq!(client.stream_withdrawable(&id), 200);
+
+    client.unfreeze(&worker, &owner);
+    assert_eq!(client.withdraw_stream(&id), 200);
+    assert_eq!(client.balance(&worker), 200);
+    assert_eq!(client.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn resume_stream_fails_when_not_paused() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_stream(&employer, &Address::generate(&e), &500, &2);
+    client.resume_stream(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #40)")]
+fn cancel_stream_fails_when_already_cancelled() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_stream(&employer, &Address::generate(&e), &500, &2);
+    client.cancel_stream(&id);
+    client.cancel_stream(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn withdraw_stream_fails_without_worker_auth() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let id = client.create_stream(&employer, &worker, &500, &2);
+    e.ledger().set_sequence_number(50);
+    e.set_auths(&[]);
+    client.withdraw_stream(&id);
+}
+
+#[test]
+fn release_credits_worker_reputation() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.set_fee_config(&100, &Address::generate(&e));
+    client.lock(&employer, &1, &worker, &500, &1000);
+    client.submit_delivery(&1);
+    client.release(&1, &employer);
+
+    let expected =
+        Reputation { jobs_completed: 1, jobs_on_time: 1, total_earned: 495, disputes_lost: 0 };
+    assert_eq!(client.reputation(&worker), expected);
+    assert_eq!(client.on_time_rate(&worker), Some(10_000));
+    assert_eq!(client.reputation(&employer), Reputation::default());
+}
+
+#[test]
+fn milestone_escrow_counts_as_completed_after_last_release() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &7, &worker, &vec![&e, 100, 250], &1000);
+    client.deliver_milestone(&7, &0);
+    client.approve_milestone(&employer, &7, &0);
+    assert_eq!(client.reputation(&worker).jobs_completed, 0);
+    assert_eq!(client.reputation(&worker).total_earned, 100);
+    assert_eq!(client.on_time_rate(&worker), None);
+
+    client.deliver_milestone(&7, &1);
+    client.approve_milestone(&employer, &7, &1);
+    assert_eq!(client.reputation(&worker).jobs_completed, 1);
+    assert_eq!(client.reputation(&worker).total_earned, 350);
+}
+
+#[test]
+fn late_delivery_lowers_on_time_rate() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &100);
+    client.lock(&employer, &2, &worker, &100, &1000);
+    e.ledger().set_sequence_number(150);
+    client.submit_delivery(&1);
+    client.submit_delivery(&2);
+    client.release(&1, &employer);
+    client.release(&2, &employer);
+
+    assert_eq!(client.reputation(&worker).jobs_completed, 2);
+    assert_eq!(client.reputation(&worker).jobs_on_time, 1);
+    assert_eq!(client.on_time_rate(&worker), Some(5_000));
+}
+
+#[test]
+fn late_release_without_delivery_lowers_on_time_rate() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.lock(&employer, &2, &worker, &100, &1000);
+    client.release(&1, &employer);
+    e.ledger().set_sequence_number(1100);
+    client.release(&2, &employer);
+
+    assert_eq!(client.reputation(&worker).jobs_completed, 2);
+    assert_eq!(client.on_time_rate(&worker), Some(5_000));
+}
+
+#[test]
+fn refund_and_ruling_clear_delivery_record() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+    let delivery_recorded = |job_id: u64| {
+        e.as_contract(&client.address, || {
+            e.storage().persistent().has(&ReputationStorageKey::LastDelivery(job_id))
+        })
+    };
+
+    e.mock_all_auths();
+    client.lock_milestones(&employer, &1, &worker, &vec![&e, 100, 200], &1000);
+    client.lock(&employer, &2, &worker, &300, &1000);
+    client.deliver_milestone(&1, &0);
+    client.submit_delivery(&2);
+    assert!(delivery_recorded(1) && delivery_recorded(2));
+
+    client.approve_milestone(&employer, &1, &0);
+    e.ledger().set_sequence_number(1000 + client.grace_period() + 1);
+    client.refund(&1);
+    client.open_dispute(&employer, &2);
+    client.resolve_dispute(&employer, &2, &5_000);
+    assert!(!delivery_recorded(1) && !delivery_recorded(2));
+}
+
+#[test]
+fn resolve_dispute_records_losing_party() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.open_dispute(&employer, &1);
+    client.resolve_dispute(&employer, &1, &2_000);
+
+    client.lock(&employer, &2, &worker, &100, &1000);
+    client.open_dispute(&worker, &2);
+    client.resolve_dispute(&employer, &2, &8_000);
+
+    let expected =
+        Reputation { jobs_completed: 0, jobs_on_time: 0, total_earned: 100, disputes_lost: 1 };
+    assert_eq!(client.reputation(&worker), expected);
+    assert_eq!(client.reputation(&employer).disputes_lost, 1);
+}
+
+#[test]
+fn parties_rate_each_other_after_release() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+    let review = BytesN::from_array(&e, &[7; 32]);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &alice, &100, &1000);
+    client.lock(&employer, &2, &bob, &100, &1000);
+    client.release(&1, &employer);
+    client.release(&2, &employer);
+
+    client.rate(&alice, &1, &4, &review);
+    client.rate(&bob, &2, &5, &review);
+    client.rate(&employer, &1, &3, &review);
+
+    let rating = client.get_rating(&1, &alice).unwrap();
+    assert_eq!(rating.ratee, employer);
+    assert_eq!(rating.score, 4);
+    assert_eq!(rating.review_hash, review);
+    assert_eq!(client.get_rating(&2, &employer), None);
+
+    assert_eq!(client.rating_summary(&employer), RatingSummary { count: 2, total_score: 9 });
+    assert_eq!(client.average_rating(&employer), Some(450));
+    assert_eq!(client.average_rating(&alice), Some(300));
+    assert_eq!(client.average_rating(&bob), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn rate_fails_when_rating_twice() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+    let review = BytesN::from_array(&e, &[7; 32]);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.release(&1, &employer);
+    client.rate(&worker, &1, &4, &review);
+    client.rate(&worker, &1, &5, &review);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn rate_fails_before_release() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.rate(&employer, &1, &4, &BytesN::from_array(&e, &[7; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn rate_fails_for_outsider() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.release(&1, &employer);
+    client.rate(&Address::generate(&e), &1, &1, &BytesN::from_array(&e, &[7; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #43)")]
+fn rate_fails_for_score_out_of_range() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let worker = Address::generate(&e);
+    let client = create_client(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    client.lock(&employer, &1, &worker, &100, &1000);
+    client.release(&1, &employer);
+    client.rate(&employer, &1, &6, &BytesN::from_array(&e, &[7; 32]));
+}
@@ -0,0 +1,156 @@
+//! Timelocked contract upgrades.
+//!
+//! Swapping the contract code could replace the escrow logic, so upgrades are
+//! never applied on the spot. An upgrader first schedules the new wasm hash,
+//! which is announced in an event, and it can only be installed once
+//! [`UPGRADE_DELAY`] ledgers have passed. Until then the upgrade can be
+//! cancelled, and holders of escrowed JBT have time to react.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, BytesN, Env};
+
+use crate::{contract::ExampleContractError, escrow::DAY_IN_LEDGERS};
+
+/// Minimum number of ledgers between scheduling and executing an upgrade.
+pub const UPGRADE_DELAY: u32 = 2 * DAY_IN_LEDGERS;
+
+/// An upgrade waiting for its delay to pass.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub scheduled_ledger: u32,
+    /// First ledger at which the upgrade can be executed.
+    pub executable_ledger: u32,
+}
+
+/// Storage keys for the upgrade timelock.
+#[contracttype]
+pub enum UpgradeStorageKey {
+    /// Maps to the [`PendingUpgrade`], unset while none is scheduled.
+    Pending,
+}
+
+/// Returns the scheduled upgrade, if any.
+pub fn pending_upgrade(e: &Env) -> Option<PendingUpgrade> {
+    e.storage().instance().get(&UpgradeStorageKey::Pending)
+}
+
+/// Schedules an upgrade to `wasm_hash`, executable after [`UPGRADE_DELAY`].
+///
+/// # Errors
+///
+/// * [`ExampleContractError::UpgradeAlreadyScheduled`] - When another upgrade
+///   is pending, it has to be cancelled first.
+///
+/// # Events
+///
+/// * topics - `["upgrade_scheduled", wasm_hash: BytesN<32>]`
+/// * data - `[executable_ledger: u32]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn schedule_upgrade(e: &Env, wasm_hash: &BytesN<32>) -> PendingUpgrade {
+    if pending_upgrade(e).is_some() {
+        panic_with_error!(e, ExampleContractError::UpgradeAlreadyScheduled);
+    }
+    let scheduled_ledger = e.ledger().sequence();
+    let upgrade = PendingUpgrade {
+        wasm_hash: wasm_hash.clone(),
+        scheduled_ledger,
+        executable_ledger: scheduled_ledger + UPGRADE_DELAY,
+    };
+    e.storage().instance().set(&UpgradeStorageKey::Pending, &upgrade);
+
+    UpgradeScheduled { wasm_hash: wasm_hash.clone(), executable_ledger: upgrade.executable_ledger }
+        .publish(e);
+    upgrade
+}
+
+/// Drops the scheduled upgrade.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::UpgradeNotScheduled`] - When no upgrade is
+///   pending.
+///
+/// # Events
+///
+/// * topics - `["upgrade_cancelled", wasm_hash: BytesN<32>]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn cancel_upgrade(e: &Env) {
+    let upgrade = read_pending_upgrade(e);
+    e.storage().instance().remove(&UpgradeStorageKey::Pending);
+
+    UpgradeCancelled { wasm_hash: upgrade.wasm_hash }.publish(e);
+}
+
+/// Takes the scheduled upgrade out of storage once its delay has passed and
+/// returns the wasm hash to install.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::UpgradeNotScheduled`] - When no upgrade is
+///   pending.
+/// * [`ExampleContractError::UpgradeNotReady`] - When the delay has not
+///   passed yet.
+///
+/// # Events
+///
+/// * topics - `["upgrade_executed", wasm_hash: BytesN<32>]`
+/// * data - `[]`
+///
+/// # Notes
+///
+/// This function only clears the bookkeeping, installing the code is up to
+/// the caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn take_ready_upgrade(e: &Env) -> BytesN<32> {
+    let upgrade = read_pending_upgrade(e);
+    if e.ledger().sequence() < upgrade.executable_ledger {
+        panic_with_error!(e, ExampleContractError::UpgradeNotReady);
+    }
+    e.storage().instance().remove(&UpgradeStorageKey::Pending);
+
+    UpgradeExecuted { wasm_hash: upgrade.wasm_hash.clone() }.publish(e);
+    upgrade.wasm_hash
+}
+
+fn read_pending_upgrade(e: &Env) -> PendingUpgrade {
+    pending_upgrade(e)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::UpgradeNotScheduled))
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when an upgrade is scheduled.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeScheduled {
+    #[topic]
+    pub wasm_hash: BytesN<32>,
+    pub executable_ledger: u32,
+}
+
+/// Event emitted when a scheduled upgrade is cancelled.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCancelled {
+    #[topic]
+    pub wasm_hash: BytesN<32>,
+}
+
+/// Event emitted right before the scheduled code is installed.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeExecuted {
+    #[topic]
+    pub wasm_hash: BytesN<32>,
+}
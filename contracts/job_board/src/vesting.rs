@@ -0,0 +1,258 @@
+//! Vesting schedules for token grants.
+//!
+//! Instead of minting a grant straight to its recipient, the owner can mint
+//! it into the contract under a [`VestingSchedule`]. Nothing vests before the
+//! cliff; from then on the grant vests linearly between the start and the
+//! end ledger, as if it had been accruing since the start. The beneficiary
+//! claims whatever has vested at any time. The owner can revoke a schedule,
+//! after which nothing more vests and the unvested part is burned, while the
+//! beneficiary can still claim what had vested until then.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env};
+
+use crate::{
+    contract::ExampleContractError,
+    escrow::{ESCROW_EXTEND_AMOUNT, ESCROW_TTL_THRESHOLD},
+};
+
+/// A grant vesting to `beneficiary` over time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub id: u32,
+    pub beneficiary: Address,
+    /// Amount granted, including what was later revoked.
+    pub total: i128,
+    pub claimed: i128,
+    pub start_ledger: u32,
+    /// First ledger at which anything is vested.
+    pub cliff_ledger: u32,
+    /// Ledger at which the whole grant is vested.
+    pub end_ledger: u32,
+    /// Ledger at which the owner revoked the schedule, if they did.
+    pub revoked_ledger: Option<u32>,
+}
+
+/// Storage keys for vesting schedules.
+#[contracttype]
+pub enum VestingStorageKey {
+    /// Maps a schedule id to its [`VestingSchedule`].
+    Schedule(u32),
+    /// Number of schedules ever created, also the next schedule id.
+    ScheduleCount,
+}
+
+/// Returns the schedule with id `id`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::VestingNotFound`] - When no such schedule
+///   exists.
+pub fn read_schedule(e: &Env, id: u32) -> VestingSchedule {
+    let key = VestingStorageKey::Schedule(id);
+    let schedule = e
+        .storage()
+        .persistent()
+        .get::<_, VestingSchedule>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, ExampleContractError::VestingNotFound));
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+    schedule
+}
+
+fn write_schedule(e: &Env, schedule: &VestingSchedule) {
+    let key = VestingStorageKey::Schedule(schedule.id);
+    e.storage().persistent().set(&key, schedule);
+    e.storage().persistent().extend_ttl(&key, ESCROW_TTL_THRESHOLD, ESCROW_EXTEND_AMOUNT);
+}
+
+/// Returns the number of schedules ever created.
+pub fn schedule_count(e: &Env) -> u32 {
+    e.storage().instance().get(&VestingStorageKey::ScheduleCount).unwrap_or(0)
+}
+
+/// Returns the amount of `schedule` vested at the current ledger, claimed or
+/// not.
+pub fn vested_amount(e: &Env, schedule: &VestingSchedule) -> i128 {
+    let ledger = match schedule.revoked_ledger {
+        Some(revoked) => revoked,
+        None => e.ledger().sequence(),
+    };
+    if ledger < schedule.cliff_ledger {
+        0
+    } else if ledger >= schedule.end_ledger {
+        schedule.total
+    } else {
+        let elapsed = (ledger - schedule.start_ledger) as i128;
+        let duration = (schedule.end_ledger - schedule.start_ledger) as i128;
+        schedule.total * elapsed / duration
+    }
+}
+
+/// Creates a schedule granting `total` to `beneficiary`.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::InvalidAmount`] - When `total` is not positive.
+/// * [`ExampleContractError::InvalidSchedule`] - When the ledgers are not
+///   ordered as `start_ledger <= cliff_ledger <= end_ledger` with
+///   `start_ledger < end_ledger`.
+///
+/// # Events
+///
+/// * topics - `["vesting_created", id: u32, beneficiary: Address]`
+/// * data - `[total: i128, start_ledger: u32, cliff_ledger: u32, end_ledger:
+///   u32]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, funding the schedule is up to the
+/// caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn create_schedule(
+    e: &Env,
+    beneficiary: &Address,
+    total: i128,
+    start_ledger: u32,
+    cliff_ledger: u32,
+    end_ledger: u32,
+) -> u32 {
+    if total <= 0 {
+        panic_with_error!(e, ExampleContractError::InvalidAmount);
+    }
+    if start_ledger > cliff_ledger || cliff_ledger > end_ledger || start_ledger == end_ledger {
+        panic_with_error!(e, ExampleContractError::InvalidSchedule);
+    }
+
+    let id = schedule_count(e);
+    write_schedule(
+        e,
+        &VestingSchedule {
+            id,
+            beneficiary: beneficiary.clone(),
+            total,
+            claimed: 0,
+            start_ledger,
+            cliff_ledger,
+            end_ledger,
+            revoked_ledger: None,
+        },
+    );
+    e.storage().instance().set(&VestingStorageKey::ScheduleCount, &(id + 1));
+
+    VestingCreated {
+        id,
+        beneficiary: beneficiary.clone(),
+        total,
+        start_ledger,
+        cliff_ledger,
+        end_ledger,
+    }
+    .publish(e);
+    id
+}
+
+/// Marks everything vested but not yet claimed in schedule `id` as claimed
+/// and returns that amount.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::VestingNotFound`] - When no such schedule
+///   exists.
+///
+/// # Events
+///
+/// * topics - `["vesting_claimed", id: u32, beneficiary: Address]`
+/// * data - `[amount: i128]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, paying the beneficiary is up to
+/// the caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn claim(e: &Env, id: u32) -> i128 {
+    let mut schedule = read_schedule(e, id);
+    let amount = vested_amount(e, &schedule) - schedule.claimed;
+    schedule.claimed += amount;
+    write_schedule(e, &schedule);
+
+    VestingClaimed { id, beneficiary: schedule.beneficiary, amount }.publish(e);
+    amount
+}
+
+/// Stops schedule `id` from vesting any further and returns the unvested
+/// amount.
+///
+/// # Errors
+///
+/// * [`ExampleContractError::VestingNotFound`] - When no such schedule
+///   exists.
+/// * [`ExampleContractError::VestingRevoked`] - When the schedule was already
+///   revoked.
+///
+/// # Events
+///
+/// * topics - `["vesting_revoked", id: u32]`
+/// * data - `[unvested: i128]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, disposing of the unvested tokens
+/// is up to the caller.
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn revoke(e: &Env, id: u32) -> i128 {
+    let mut schedule = read_schedule(e, id);
+    if schedule.revoked_ledger.is_some() {
+        panic_with_error!(e, ExampleContractError::VestingRevoked);
+    }
+    schedule.revoked_ledger = Some(e.ledger().sequence());
+    let unvested = schedule.total - vested_amount(e, &schedule);
+    write_schedule(e, &schedule);
+
+    VestingRevoked { id, unvested }.publish(e);
+    unvested
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when the owner creates a vesting schedule.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingCreated {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub beneficiary: Address,
+    pub total: i128,
+    pub start_ledger: u32,
+    pub cliff_ledger: u32,
+    pub end_ledger: u32,
+}
+
+/// Event emitted when a beneficiary claims vested tokens.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingClaimed {
+    #[topic]
+    pub id: u32,
+    #[topic]
+    pub beneficiary: Address,
+    pub amount: i128,
+}
+
+/// Event emitted when the owner revokes a vesting schedule.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingRevoked {
+    #[topic]
+    pub id: u32,
+    pub unvested: i128,
+}
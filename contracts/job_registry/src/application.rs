@@ -0,0 +1,261 @@
+//! Job applications and hiring.
+//!
+//! Candidates apply to `Open` jobs, the employer shortlists or rejects them and
+//! eventually hires one. Hiring binds the escrowed job budget to the hired
+//! candidate in the JBT token contract.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env, String, Vec};
+
+use crate::{
+    contract::JobRegistryError,
+    job::{JOB_EXTEND_AMOUNT, JOB_TTL_THRESHOLD},
+};
+
+/// Lifecycle of an application.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ApplicationStatus {
+    Applied,
+    Shortlisted,
+    Hired,
+    Rejected,
+}
+
+/// A candidate's application to a job.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Application {
+    pub job_id: u64,
+    pub candidate: Address,
+    pub proposal_uri: String,
+    /// Amount asked by the candidate, informational only: hiring escrows the
+    /// posted budget.
+    pub bid_amount: i128,
+    pub status: ApplicationStatus,
+    pub applied_ledger: u32,
+}
+
+/// Storage keys for the applications.
+#[contracttype]
+pub enum ApplicationStorageKey {
+    /// Maps `(job_id, candidate)` to its [`Application`].
+    Application(u64, Address),
+    /// Number of applications received by a job.
+    Count(u64),
+    /// Maps `(job_id, index)` to a candidate, used for enumeration.
+    Index(u64, u32),
+}
+
+/// Returns the number of applications received by `job_id`.
+pub fn application_count(e: &Env, job_id: u64) -> u32 {
+    let key = ApplicationStorageKey::Count(job_id);
+    let count = e.storage().persistent().get::<_, u32>(&key);
+    if count.is_some() {
+        e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+    }
+    count.unwrap_or(0)
+}
+
+/// Returns the application of `candidate` to `job_id`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::ApplicationNotFound`] - When the candidate did not
+///   apply.
+pub fn read_application(e: &Env, job_id: u64, candidate: &Address) -> Application {
+    let key = ApplicationStorageKey::Application(job_id, candidate.clone());
+    let application = e
+        .storage()
+        .persistent()
+        .get::<_, Application>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, JobRegistryError::ApplicationNotFound));
+    e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+    application
+}
+
+fn write_application(e: &Env, application: &Application) {
+    let key = ApplicationStorageKey::Application(application.job_id, application.candidate.clone());
+    e.storage().persistent().set(&key, application);
+    e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+}
+
+/// Records a new application of `candidate` to `job_id`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::AlreadyApplied`] - When the candidate already applied.
+///
+/// # Events
+///
+/// * topics - `["application_submitted", job_id: u64, candidate: Address]`
+/// * data - `[bid_amount: i128]`
+///
+/// # Notes
+///
+/// Authorization for `candidate` and the job being `Open` are expected to be
+/// checked by the caller.
+pub fn apply(
+    e: &Env,
+    job_id: u64,
+    candidate: &Address,
+    proposal_uri: &String,
+    bid_amount: i128,
+) -> Application {
+    let key = ApplicationStorageKey::Application(job_id, candidate.clone());
+    if e.storage().persistent().has(&key) {
+        panic_with_error!(e, JobRegistryError::AlreadyApplied);
+    }
+
+    let application = Application {
+        job_id,
+        candidate: candidate.clone(),
+        proposal_uri: proposal_uri.clone(),
+        bid_amount,
+        status: ApplicationStatus::Applied,
+        applied_ledger: e.ledger().sequence(),
+    };
+    write_application(e, &application);
+
+    let index = application_count(e, job_id);
+    let index_key = ApplicationStorageKey::Index(job_id, index);
+    e.storage().persistent().set(&index_key, candidate);
+    e.storage().persistent().extend_ttl(&index_key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+    let count_key = ApplicationStorageKey::Count(job_id);
+    e.storage().persistent().set(&count_key, &(index + 1));
+    e.storage().persistent().extend_ttl(&count_key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+
+    ApplicationSubmitted { job_id, candidate: candidate.clone(), bid_amount }.publish(e);
+    application
+}
+
+/// Moves an `Applied` application to `Shortlisted`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::ApplicationNotFound`] - When the candidate did not
+///   apply.
+/// * [`JobRegistryError::InvalidApplicationState`] - When the application is
+///   not `Applied`.
+///
+/// # Events
+///
+/// * topics - `["candidate_shortlisted", job_id: u64, candidate: Address]`
+/// * data - `[]`
+pub fn shortlist(e: &Env, job_id: u64, candidate: &Address) {
+    let mut application = read_application(e, job_id, candidate);
+    if application.status != ApplicationStatus::Applied {
+        panic_with_error!(e, JobRegistryError::InvalidApplicationState);
+    }
+    application.status = ApplicationStatus::Shortlisted;
+    write_application(e, &application);
+
+    CandidateShortlisted { job_id, candidate: candidate.clone() }.publish(e);
+}
+
+/// Moves an `Applied` or `Shortlisted` application to `Rejected`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::ApplicationNotFound`] - When the candidate did not
+///   apply.
+/// * [`JobRegistryError::InvalidApplicationState`] - When the application is
+///   already `Hired` or `Rejected`.
+///
+/// # Events
+///
+/// * topics - `["candidate_rejected", job_id: u64, candidate: Address]`
+/// * data - `[]`
+pub fn reject(e: &Env, job_id: u64, candidate: &Address) {
+    let mut application = read_application(e, job_id, candidate);
+    ensure_pending(e, &application);
+    application.status = ApplicationStatus::Rejected;
+    write_application(e, &application);
+
+    CandidateRejected { job_id, candidate: candidate.clone() }.publish(e);
+}
+
+/// Moves an `Applied` or `Shortlisted` application to `Hired`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::ApplicationNotFound`] - When the candidate did not
+///   apply.
+/// * [`JobRegistryError::InvalidApplicationState`] - When the application is
+///   already `Hired` or `Rejected`.
+///
+/// # Events
+///
+/// * topics - `["candidate_hired", job_id: u64, candidate: Address]`
+/// * data - `[]`
+pub fn hire(e: &Env, job_id: u64, candidate: &Address) {
+    let mut application = read_application(e, job_id, candidate);
+    ensure_pending(e, &application);
+    application.status = ApplicationStatus::Hired;
+    write_application(e, &application);
+
+    CandidateHired { job_id, candidate: candidate.clone() }.publish(e);
+}
+
+/// Returns up to `limit` applications to `job_id` in submission order,
+/// starting at `start`.
+pub fn list_applications(e: &Env, job_id: u64, start: u32, limit: u32) -> Vec<Application> {
+    let mut applications = Vec::new(e);
+    let end = start.saturating_add(limit).min(application_count(e, job_id));
+    for index in start..end {
+        let key = ApplicationStorageKey::Index(job_id, index);
+        let candidate: Address =
+            e.storage().persistent().get(&key).expect("application index should be set");
+        e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+        applications.push_back(read_application(e, job_id, &candidate));
+    }
+    applications
+}
+
+fn ensure_pending(e: &Env, application: &Application) {
+    if !matches!(application.status, ApplicationStatus::Applied | ApplicationStatus::Shortlisted) {
+        panic_with_error!(e, JobRegistryError::InvalidApplicationState);
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when a candidate applies to a job.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApplicationSubmitted {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub candidate: Address,
+    pub bid_amount: i128,
+}
+
+/// Event emitted when the employer shortlists a candidate.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateShortlisted {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub candidate: Address,
+}
+
+/// Event emitted when the employer rejects a candidate.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateRejected {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub candidate: Address,
+}
+
+/// Event emitted when the employer hires a candidate.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateHired {
+    #[topic]
+    pub job_id: u64,
+    #[topic]
+    pub candidate: Address,
+}
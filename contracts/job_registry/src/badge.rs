@@ -0,0 +1,9 @@
+//! Interface of the skill badge contract the registry checks applicants
+//! against.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+#[contractclient(name = "SkillBadgeClient")]
+pub trait SkillBadge {
+    fn has_badge(e: Env, holder: Address, kind: Symbol) -> bool;
+}
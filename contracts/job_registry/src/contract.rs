@@ -0,0 +1,197 @@
+//! Job Registry Contract.
+
+//! This contract stores the job postings of the board. Publishing a job
+//! escrows its budget in the JBT token contract, closing it refunds the
+//! budget to the employer and hiring a candidate binds the budget to them.
+//! Employers can restrict a job to candidates holding a given kind of skill
+//! badge.
+
+use soroban_sdk::{
+    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, panic_with_error, symbol_short
+};
+
+use crate::{
+    application::{self, Application},
+    badge::SkillBadgeClient,
+    job::{self, Job, JobClosed, JobStatus, JobUpdated},
+    token::JobBoardTokenClient,
+};
+
+pub const TOKEN: Symbol = symbol_short!("TOKEN");
+pub const BADGES: Symbol = symbol_short!("BADGES");
+
+#[contract]
+pub struct JobRegistryContract;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum JobRegistryError {
+    JobNotFound = 1,
+    JobNotOpen = 2,
+    ApplicationNotFound = 3,
+    AlreadyApplied = 4,
+    InvalidApplicationState = 5,
+    BadgeRequired = 6,
+}
+
+#[contractimpl]
+impl JobRegistryContract {
+    pub fn __constructor(e: &Env, token: Address, badges: Address) {
+        e.storage().instance().set(&TOKEN, &token);
+        e.storage().instance().set(&BADGES, &badges);
+    }
+
+    pub fn token(e: &Env) -> Address {
+        e.storage().instance().get(&TOKEN).expect("token should be set")
+    }
+
+    pub fn badges(e: &Env) -> Address {
+        e.storage().instance().get(&BADGES).expect("badges should be set")
+    }
+
+    pub fn create_job(
+        e: &Env,
+        employer: Address,
+        title_hash: BytesN<32>,
+        description_uri: String,
+        budget: i128,
+        deadline_ledger: u32,
+    ) -> u64 {
+        // The employer authorizes both the posting and escrowing its budget.
+        // The registry manages the escrow, so the employer cannot refund or
+        // assign it behind the registry's back.
+        employer.require_auth();
+
+        let manager = Some(e.current_contract_address());
+        let escrow_id =
+            Self::token_client(e).lock_budget(&employer, &manager, &budget, &deadline_ledger);
+        let job = job::create_job(
+            e,
+            &employer,
+            &title_hash,
+            &description_uri,
+            budget,
+            escrow_id,
+            deadline_ledger,
+        );
+
+        job.id
+    }
+
+    pub fn update_job(e: &Env, job_id: u64, title_hash: BytesN<32>, description_uri: String) {
+        // Only the description can change, the budget stays as escrowed.
+        let mut job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        job.title_hash = title_hash;
+        job.description_uri = description_uri;
+        job::write_job(e, &job);
+
+        JobUpdated { id: job_id }.publish(e);
+    }
+
+    pub fn set_required_badge(e: &Env, job_id: u64, badge: Option<Symbol>) {
+        // Only applications made from now on are checked against the badge.
+        let mut job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        job.required_badge = badge;
+        job::write_job(e, &job);
+
+        JobUpdated { id: job_id }.publish(e);
+    }
+
+    pub fn close_job(e: &Env, job_id: u64) {
+        let mut job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        job.status = JobStatus::Closed;
+        job::write_job(e, &job);
+        Self::token_client(e).refund(&job.escrow_id);
+
+        JobClosed { id: job_id }.publish(e);
+    }
+
+    pub fn apply(
+        e: &Env,
+        job_id: u64,
+        candidate: Address,
+        proposal_uri: String,
+        bid_amount: i128,
+    ) {
+        candidate.require_auth();
+        let job = job::read_job(e, job_id);
+        job::ensure_open(e, &job);
+        if let Some(badge) = &job.required_badge {
+            if !SkillBadgeClient::new(e, &Self::badges(e)).has_badge(&candidate, badge) {
+                panic_with_error!(e, JobRegistryError::BadgeRequired);
+            }
+        }
+
+        application::apply(e, job_id, &candidate, &proposal_uri, bid_amount);
+    }
+
+    pub fn shortlist(e: &Env, job_id: u64, candidate: Address) {
+        let job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        application::shortlist(e, job_id, &candidate);
+    }
+
+    pub fn reject(e: &Env, job_id: u64, candidate: Address) {
+        let job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        application::reject(e, job_id, &candidate);
+    }
+
+    pub fn hire(e: &Env, job_id: u64, candidate: Address) {
+        // The employer authorizes both the hire and binding the escrowed
+        // budget to the candidate.
+        let mut job = job::read_job(e, job_id);
+        job.employer.require_auth();
+        job::ensure_open(e, &job);
+
+        application::hire(e, job_id, &candidate);
+        job.status = JobStatus::Filled;
+        job.worker = Some(candidate.clone());
+        job::write_job(e, &job);
+        Self::token_client(e).assign_worker(&job.escrow_id, &candidate);
+    }
+
+    pub fn get_application(e: &Env, job_id: u64, candidate: Address) -> Application {
+        application::read_application(e, job_id, &candidate)
+    }
+
+    pub fn application_count(e: &Env, job_id: u64) -> u32 {
+        application::application_count(e, job_id)
+    }
+
+    pub fn list_applications(e: &Env, job_id: u64, start: u32, limit: u32) -> Vec<Application> {
+        application::list_applications(e, job_id, start, limit)
+    }
+
+    pub fn get_job(e: &Env, job_id: u64) -> Job {
+        job::read_job(e, job_id)
+    }
+
+    pub fn job_count(e: &Env) -> u64 {
+        job::job_count(e)
+    }
+
+    pub fn list_jobs(e: &Env, start: u64, limit: u32) -> Vec<Job> {
+        job::list_jobs(e, start, limit)
+    }
+}
+
+impl JobRegistryContract {
+    fn token_client(e: &Env) -> JobBoardTokenClient<'_> {
+        JobBoardTokenClient::new(e, &Self::token(e))
+    }
+}
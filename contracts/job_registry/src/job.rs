@@ -0,0 +1,180 @@
+//! Job posting records.
+//!
+//! Job ids are allocated sequentially by the registry. The budget of a job is
+//! escrowed in the JBT token contract under an escrow id the token allocates,
+//! with the registry as the manager of the escrow, so only the registry can
+//! refund the budget or bind it to a worker.
+
+use soroban_sdk::{
+    contractevent, contracttype, panic_with_error, Address, BytesN, Env, String, Symbol, Vec,
+};
+
+use crate::contract::JobRegistryError;
+
+const DAY_IN_LEDGERS: u32 = 17280;
+pub const JOB_EXTEND_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const JOB_TTL_THRESHOLD: u32 = JOB_EXTEND_AMOUNT - DAY_IN_LEDGERS;
+
+/// Lifecycle of a job posting.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum JobStatus {
+    /// Published and accepting work, the budget is escrowed.
+    Open,
+    /// Withdrawn by the employer, the budget was refunded.
+    Closed,
+    /// A candidate was hired, the budget is escrowed for them.
+    Filled,
+}
+
+/// A job posted on the board.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Job {
+    pub id: u64,
+    pub employer: Address,
+    /// Hash of the job title, the title itself is kept off-chain.
+    pub title_hash: BytesN<32>,
+    pub description_uri: String,
+    /// Budget in JBT, escrowed in the token contract while the job is open.
+    pub budget: i128,
+    /// Id of the escrow holding the budget in the token contract.
+    pub escrow_id: u64,
+    pub status: JobStatus,
+    pub deadline_ledger: u32,
+    pub created_ledger: u32,
+    /// Set once a candidate is hired.
+    pub worker: Option<Address>,
+    /// Kind of skill badge candidates must hold to apply, if any.
+    pub required_badge: Option<Symbol>,
+}
+
+/// Storage keys for the job postings.
+#[contracttype]
+pub enum JobStorageKey {
+    /// Maps a job id to its [`Job`].
+    Job(u64),
+    /// Number of jobs ever created, also the next job id.
+    Count,
+}
+
+/// Returns the number of jobs ever created.
+pub fn job_count(e: &Env) -> u64 {
+    e.storage().instance().get(&JobStorageKey::Count).unwrap_or(0)
+}
+
+/// Returns the job with id `job_id`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::JobNotFound`] - When no such job exists.
+pub fn read_job(e: &Env, job_id: u64) -> Job {
+    let key = JobStorageKey::Job(job_id);
+    let job = e
+        .storage()
+        .persistent()
+        .get::<_, Job>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, JobRegistryError::JobNotFound));
+    e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+    job
+}
+
+/// Stores `job` under its id.
+pub fn write_job(e: &Env, job: &Job) {
+    let key = JobStorageKey::Job(job.id);
+    e.storage().persistent().set(&key, job);
+    e.storage().persistent().extend_ttl(&key, JOB_TTL_THRESHOLD, JOB_EXTEND_AMOUNT);
+}
+
+/// Creates an `Open` job under the next free id.
+///
+/// # Events
+///
+/// * topics - `["job_created", id: u64, employer: Address]`
+/// * data - `[budget: i128, deadline_ledger: u32]`
+///
+/// # Notes
+///
+/// This function only writes bookkeeping, escrowing the budget is up to the
+/// caller.
+pub fn create_job(
+    e: &Env,
+    employer: &Address,
+    title_hash: &BytesN<32>,
+    description_uri: &String,
+    budget: i128,
+    escrow_id: u64,
+    deadline_ledger: u32,
+) -> Job {
+    let id = job_count(e);
+    let job = Job {
+        id,
+        employer: employer.clone(),
+        title_hash: title_hash.clone(),
+        description_uri: description_uri.clone(),
+        budget,
+        escrow_id,
+        status: JobStatus::Open,
+        deadline_ledger,
+        created_ledger: e.ledger().sequence(),
+        worker: None,
+        required_badge: None,
+    };
+    write_job(e, &job);
+    e.storage().instance().set(&JobStorageKey::Count, &(id + 1));
+
+    JobCreated { id, employer: employer.clone(), budget, deadline_ledger }.publish(e);
+    job
+}
+
+/// Returns up to `limit` jobs in creation order, starting at id `start`.
+pub fn list_jobs(e: &Env, start: u64, limit: u32) -> Vec<Job> {
+    let mut jobs = Vec::new(e);
+    let end = start.saturating_add(limit as u64).min(job_count(e));
+    for id in start..end {
+        jobs.push_back(read_job(e, id));
+    }
+    jobs
+}
+
+/// Panics unless `job` is `Open`.
+///
+/// # Errors
+///
+/// * [`JobRegistryError::JobNotOpen`] - When the job is not `Open`.
+pub fn ensure_open(e: &Env, job: &Job) {
+    if job.status != JobStatus::Open {
+        panic_with_error!(e, JobRegistryError::JobNotOpen);
+    }
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when a job is published.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobCreated {
+    #[topic]
+    pub id: u64,
+    #[topic]
+    pub employer: Address,
+    pub budget: i128,
+    pub deadline_ledger: u32,
+}
+
+/// Event emitted when the employer edits a job description or its badge
+/// requirement.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobUpdated {
+    #[topic]
+    pub id: u64,
+}
+
+/// Event emitted when the employer closes a job.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobClosed {
+    #[topic]
+    pub id: u64,
+}
@@ -0,0 +1,10 @@
+#![no_std]
+#![allow(dead_code)]
+
+mod application;
+mod badge;
+pub mod contract;
+mod job;
+mod token;
+#[cfg(test)]
+mod test;
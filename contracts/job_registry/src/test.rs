@@ -0,0 +1,332 @@
+extern crate std;
+
+use job_board::contract::{ExampleContract, ExampleContractClient};
+use skill_badge::contract::{SkillBadgeContract, SkillBadgeContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal, String, Symbol,
+};
+
+use crate::{
+    application::ApplicationStatus,
+    contract::{JobRegistryContract, JobRegistryContractClient},
+    job::JobStatus,
+};
+
+fn create_clients<'a>(
+    e: &Env,
+    owner: &Address,
+    initial_supply: i128,
+) -> (ExampleContractClient<'a>, JobRegistryContractClient<'a>) {
+    let token = e.register(ExampleContract, (owner, initial_supply, 1_000_000_i128));
+    let badges = e.register(SkillBadgeContract, (owner, String::from_str(e, "ipfs://badges/")));
+    let registry = e.register(JobRegistryContract, (&token, &badges));
+    (ExampleContractClient::new(e, &token), JobRegistryContractClient::new(e, &registry))
+}
+
+fn badges_client<'a>(
+    e: &Env,
+    registry: &JobRegistryContractClient,
+) -> SkillBadgeContractClient<'a> {
+    SkillBadgeContractClient::new(e, &registry.badges())
+}
+
+fn create_job(e: &Env, registry: &JobRegistryContractClient, employer: &Address) -> u64 {
+    registry.create_job(
+        employer,
+        &BytesN::from_array(e, &[1; 32]),
+        &String::from_str(e, "ipfs://job"),
+        &300,
+        &1000,
+    )
+}
+
+#[test]
+fn create_job_escrows_budget() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &employer, 1000);
+    let title_hash = BytesN::from_array(&e, &[1; 32]);
+    let uri = String::from_str(&e, "ipfs://job");
+
+    e.mock_all_auths();
+    let job_id = registry.create_job(&employer, &title_hash, &uri, &300, &1000);
+    assert_eq!(job_id, 0);
+    assert_eq!(token.balance(&employer), 700);
+    assert_eq!(token.balance(&token.address), 300);
+
+    let job = registry.get_job(&job_id);
+    assert_eq!(token.get_escrow(&job.escrow_id).employer, employer);
+    assert_eq!(token.escrow_manager(&job.escrow_id), Some(registry.address.clone()));
+    assert_eq!(job.employer, employer);
+    assert_eq!(job.title_hash, title_hash);
+    assert_eq!(job.description_uri, uri);
+    assert_eq!(job.budget, 300);
+    assert_eq!(job.status, JobStatus::Open);
+    assert_eq!(job.deadline_ledger, 1000);
+}
+
+#[test]
+fn update_job_changes_description() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = registry.create_job(
+        &employer,
+        &BytesN::from_array(&e, &[1; 32]),
+        &String::from_str(&e, "ipfs://job"),
+        &300,
+        &1000,
+    );
+    registry.update_job(&job_id, &BytesN::from_array(&e, &[2; 32]), &String::from_str(&e, "ipfs://v2"));
+
+    let job = registry.get_job(&job_id);
+    assert_eq!(job.title_hash, BytesN::from_array(&e, &[2; 32]));
+    assert_eq!(job.description_uri, String::from_str(&e, "ipfs://v2"));
+}
+
+#[test]
+fn close_job_refunds_budget() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = registry.create_job(
+        &employer,
+        &BytesN::from_array(&e, &[1; 32]),
+        &String::from_str(&e, "ipfs://job"),
+        &300,
+        &1000,
+    );
+    registry.close_job(&job_id);
+    assert_eq!(registry.get_job(&job_id).status, JobStatus::Closed);
+    assert_eq!(token.balance(&employer), 1000);
+}
+
+#[test]
+fn create_job_ignores_escrows_locked_by_others() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let employer = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &owner, 1000);
+
+    e.mock_all_auths();
+    token.transfer(&owner, &employer, &600);
+    // Escrows locked directly on the token cannot take the id of a budget.
+    token.lock(&owner, &registry.job_count(), &employer, &1, &1000);
+    token.lock_budget(&owner, &None, &1, &1000);
+
+    let first = create_job(&e, &registry, &employer);
+    let second = create_job(&e, &registry, &employer);
+    assert_ne!(registry.get_job(&first).escrow_id, registry.get_job(&second).escrow_id);
+    assert_eq!(token.balance(&employer), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn budget_cannot_be_refunded_behind_registry() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    let escrow_id = registry.get_job(&job_id).escrow_id;
+
+    token
+        .mock_auths(&[MockAuth {
+            address: &employer,
+            invoke: &MockAuthInvoke {
+                contract: &token.address,
+                fn_name: "refund",
+                args: (escrow_id,).into_val(&e),
+                sub_invokes: &[],
+            },
+        }])
+        .refund(&escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn update_job_fails_when_closed() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = registry.create_job(
+        &employer,
+        &BytesN::from_array(&e, &[1; 32]),
+        &String::from_str(&e, "ipfs://job"),
+        &300,
+        &1000,
+    );
+    registry.close_job(&job_id);
+    registry.update_job(&job_id, &BytesN::from_array(&e, &[2; 32]), &String::from_str(&e, "ipfs://v2"));
+}
+
+#[test]
+fn list_jobs_pages_in_creation_order() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+    let uri = String::from_str(&e, "ipfs://job");
+
+    e.mock_all_auths();
+    for i in 0..3u8 {
+        registry.create_job(&employer, &BytesN::from_array(&e, &[i; 32]), &uri, &100, &1000);
+    }
+    assert_eq!(registry.job_count(), 3);
+
+    let page = registry.list_jobs(&1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).id, 1);
+    assert_eq!(page.get_unchecked(1).id, 2);
+}
+
+#[test]
+fn hire_binds_escrow_to_candidate() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &employer, 1000);
+    let proposal = String::from_str(&e, "ipfs://proposal");
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.apply(&job_id, &alice, &proposal, &250);
+    registry.apply(&job_id, &bob, &proposal, &300);
+    assert_eq!(registry.application_count(&job_id), 2);
+
+    registry.shortlist(&job_id, &alice);
+    registry.reject(&job_id, &bob);
+    registry.hire(&job_id, &alice);
+
+    assert_eq!(registry.get_application(&job_id, &alice).status, ApplicationStatus::Hired);
+    assert_eq!(registry.get_application(&job_id, &bob).status, ApplicationStatus::Rejected);
+    let job = registry.get_job(&job_id);
+    assert_eq!(job.status, JobStatus::Filled);
+    assert_eq!(job.worker, Some(alice.clone()));
+    assert_eq!(token.get_escrow(&job.escrow_id).worker, Some(alice.clone()));
+
+    token.release(&job.escrow_id, &employer);
+    assert_eq!(token.balance(&alice), 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn hire_fails_after_delivery_window() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (token, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.apply(&job_id, &alice, &String::from_str(&e, "ipfs://proposal"), &250);
+
+    e.ledger().set_sequence_number(1001 + token.grace_period());
+    registry.hire(&job_id, &alice);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn apply_fails_when_already_applied() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+    let proposal = String::from_str(&e, "ipfs://proposal");
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.apply(&job_id, &alice, &proposal, &250);
+    registry.apply(&job_id, &alice, &proposal, &200);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn hire_fails_for_rejected_candidate() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.apply(&job_id, &alice, &String::from_str(&e, "ipfs://proposal"), &250);
+    registry.reject(&job_id, &alice);
+    registry.hire(&job_id, &alice);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn apply_fails_when_job_filled() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+    let proposal = String::from_str(&e, "ipfs://proposal");
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.apply(&job_id, &alice, &proposal, &250);
+    registry.hire(&job_id, &alice);
+    registry.apply(&job_id, &bob, &proposal, &250);
+}
+
+#[test]
+fn apply_checks_required_badge() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+    let badges = badges_client(&e, &registry);
+    let kind = Symbol::new(&e, "verified_rust_dev");
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.set_required_badge(&job_id, &Some(kind.clone()));
+    assert_eq!(registry.get_job(&job_id).required_badge, Some(kind.clone()));
+
+    badges.issue(&alice, &kind, &employer);
+    registry.apply(&job_id, &alice, &String::from_str(&e, "ipfs://proposal"), &250);
+    assert_eq!(registry.application_count(&job_id), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn apply_fails_without_required_badge() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.set_required_badge(&job_id, &Some(Symbol::new(&e, "verified_rust_dev")));
+    registry.apply(&job_id, &alice, &String::from_str(&e, "ipfs://proposal"), &250);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn apply_fails_after_badge_revoked() {
+    let e = Env::default();
+    let employer = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let (_, registry) = create_clients(&e, &employer, 1000);
+    let badges = badges_client(&e, &registry);
+    let kind = Symbol::new(&e, "verified_rust_dev");
+
+    e.mock_all_auths();
+    let job_id = create_job(&e, &registry, &employer);
+    registry.set_required_badge(&job_id, &Some(kind.clone()));
+    let token_id = badges.issue(&alice, &kind, &employer);
+    badges.revoke(&token_id, &employer);
+    registry.apply(&job_id, &alice, &String::from_str(&e, "ipfs://proposal"), &250);
+}
@@ -0,0 +1,18 @@
+//! Interface of the JBT token contract the registry escrows budgets with.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+#[contractclient(name = "JobBoardTokenClient")]
+pub trait JobBoardToken {
+    fn lock_budget(
+        e: Env,
+        employer: Address,
+        manager: Option<Address>,
+        amount: i128,
+        deadline_ledger: u32,
+    ) -> u64;
+
+    fn assign_worker(e: Env, job_id: u64, worker: Address);
+
+    fn refund(e: Env, job_id: u64);
+}
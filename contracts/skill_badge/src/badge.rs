@@ -0,0 +1,162 @@
+//! Badge records.
+//!
+//! Every badge is a non-fungible token with a [`Badge`] record next to it,
+//! naming the kind of credential it stands for, such as `verified_rust_dev`.
+//! An account holds at most one badge of each kind, which makes checking for
+//! a credential a single lookup.
+
+use soroban_sdk::{contractevent, contracttype, panic_with_error, Address, Env, Symbol};
+use stellar_tokens::non_fungible::{burnable::emit_burn, Base};
+
+use crate::contract::SkillBadgeError;
+
+const DAY_IN_LEDGERS: u32 = 17280;
+pub const BADGE_EXTEND_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub const BADGE_TTL_THRESHOLD: u32 = BADGE_EXTEND_AMOUNT - DAY_IN_LEDGERS;
+
+/// A credential issued to an account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub token_id: u32,
+    pub kind: Symbol,
+    pub holder: Address,
+    pub issuer: Address,
+    pub issued_ledger: u32,
+}
+
+/// Storage keys for the badge records, token ownership is stored by
+/// [`Base`].
+#[contracttype]
+pub enum BadgeStorageKey {
+    /// Maps a token id to its [`Badge`].
+    Badge(u32),
+    /// Maps a holder and a badge kind to the token id of the badge.
+    Held(Address, Symbol),
+}
+
+/// Returns the badge with token id `token_id`.
+///
+/// # Errors
+///
+/// * [`SkillBadgeError::BadgeNotFound`] - When no such badge exists.
+pub fn read_badge(e: &Env, token_id: u32) -> Badge {
+    let key = BadgeStorageKey::Badge(token_id);
+    let badge = e
+        .storage()
+        .persistent()
+        .get::<_, Badge>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, SkillBadgeError::BadgeNotFound));
+    e.storage().persistent().extend_ttl(&key, BADGE_TTL_THRESHOLD, BADGE_EXTEND_AMOUNT);
+    badge
+}
+
+/// Returns the token id of the badge of `kind` held by `holder`, if any.
+pub fn badge_of(e: &Env, holder: &Address, kind: &Symbol) -> Option<u32> {
+    let key = BadgeStorageKey::Held(holder.clone(), kind.clone());
+    let token_id = e.storage().persistent().get::<_, u32>(&key);
+    if token_id.is_some() {
+        e.storage().persistent().extend_ttl(&key, BADGE_TTL_THRESHOLD, BADGE_EXTEND_AMOUNT);
+    }
+    token_id
+}
+
+/// Mints a badge of `kind` to `holder` and returns its token id.
+///
+/// # Errors
+///
+/// * [`SkillBadgeError::AlreadyIssued`] - When `holder` already holds a
+///   badge of `kind`.
+///
+/// # Events
+///
+/// * topics - `["mint", to: Address]`
+/// * data - `[token_id: u32]`
+///
+/// * topics - `["badge_issued", token_id: u32, holder: Address, kind:
+///   Symbol]`
+/// * data - `[issuer: Address]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn issue(e: &Env, issuer: &Address, holder: &Address, kind: &Symbol) -> u32 {
+    if badge_of(e, holder, kind).is_some() {
+        panic_with_error!(e, SkillBadgeError::AlreadyIssued);
+    }
+
+    let token_id = Base::sequential_mint(e, holder);
+    let badge = Badge {
+        token_id,
+        kind: kind.clone(),
+        holder: holder.clone(),
+        issuer: issuer.clone(),
+        issued_ledger: e.ledger().sequence(),
+    };
+    let badge_key = BadgeStorageKey::Badge(token_id);
+    e.storage().persistent().set(&badge_key, &badge);
+    e.storage().persistent().extend_ttl(&badge_key, BADGE_TTL_THRESHOLD, BADGE_EXTEND_AMOUNT);
+    let held_key = BadgeStorageKey::Held(holder.clone(), kind.clone());
+    e.storage().persistent().set(&held_key, &token_id);
+    e.storage().persistent().extend_ttl(&held_key, BADGE_TTL_THRESHOLD, BADGE_EXTEND_AMOUNT);
+
+    BadgeIssued { token_id, holder: holder.clone(), kind: kind.clone(), issuer: issuer.clone() }
+        .publish(e);
+    token_id
+}
+
+/// Burns the badge with token id `token_id`.
+///
+/// # Errors
+///
+/// * [`SkillBadgeError::BadgeNotFound`] - When no such badge exists.
+///
+/// # Events
+///
+/// * topics - `["burn", from: Address]`
+/// * data - `[token_id: u32]`
+///
+/// * topics - `["badge_revoked", token_id: u32, holder: Address, kind:
+///   Symbol]`
+/// * data - `[]`
+///
+/// # Security Warning
+///
+/// **IMPORTANT**: This function lacks authorization checks.
+pub fn revoke(e: &Env, token_id: u32) {
+    let badge = read_badge(e, token_id);
+    Base::update(e, Some(&badge.holder), None, token_id);
+    emit_burn(e, &badge.holder, token_id);
+    e.storage().persistent().remove(&BadgeStorageKey::Badge(token_id));
+    let held_key = BadgeStorageKey::Held(badge.holder.clone(), badge.kind.clone());
+    e.storage().persistent().remove(&held_key);
+
+    BadgeRevoked { token_id, holder: badge.holder, kind: badge.kind }.publish(e);
+}
+
+// ################## EVENTS ##################
+
+/// Event emitted when an issuer issues a badge.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeIssued {
+    #[topic]
+    pub token_id: u32,
+    #[topic]
+    pub holder: Address,
+    #[topic]
+    pub kind: Symbol,
+    pub issuer: Address,
+}
+
+/// Event emitted when an issuer revokes a badge.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRevoked {
+    #[topic]
+    pub token_id: u32,
+    #[topic]
+    pub holder: Address,
+    #[topic]
+    pub kind: Symbol,
+}
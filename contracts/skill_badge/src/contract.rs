@@ -0,0 +1,176 @@
+//! Skill Badge Contract.
+
+//! This contract issues soulbound credentials, such as "Verified Rust
+//! Developer" or "100 Jobs Completed", as non-fungible tokens. Accounts
+//! holding the `issuer` role, managed by the access control admin, issue
+//! badges and can revoke them, which burns the token. Badges cannot be
+//! transferred or approved: they stay with the account they were issued to.
+//!
+//! The job registry can require applicants to hold a badge of a given kind.
+
+use soroban_sdk::{
+    Address, Env, String, Symbol, contract, contracterror, contractimpl, panic_with_error, symbol_short
+};
+use stellar_access::access_control::{self as access_control, AccessControl};
+use stellar_macros::only_role;
+use stellar_tokens::non_fungible::{Base, NonFungibleToken};
+
+use crate::badge::{self, Badge};
+
+pub const ISSUER_ROLE: Symbol = symbol_short!("issuer");
+
+#[contract]
+pub struct SkillBadgeContract;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SkillBadgeError {
+    NonTransferable = 1,
+    AlreadyIssued = 2,
+    BadgeNotFound = 3,
+}
+
+#[contractimpl]
+impl SkillBadgeContract {
+    pub fn __constructor(e: &Env, owner: Address, base_uri: String) {
+        Base::set_metadata(
+            e,
+            base_uri,
+            String::from_str(e, "JobBoardBadge"),
+            String::from_str(e, "JBB"),
+        );
+
+        access_control::set_admin(e, &owner);
+        access_control::grant_role_no_auth(e, &owner, &owner, &ISSUER_ROLE);
+    }
+
+    #[only_role(issuer, "issuer")]
+    pub fn issue(e: &Env, holder: Address, kind: Symbol, issuer: Address) -> u32 {
+        badge::issue(e, &issuer, &holder, &kind)
+    }
+
+    #[only_role(issuer, "issuer")]
+    pub fn revoke(e: &Env, token_id: u32, issuer: Address) {
+        // Any issuer can revoke any badge, not only the ones they issued.
+        badge::revoke(e, token_id);
+    }
+
+    pub fn get_badge(e: &Env, token_id: u32) -> Badge {
+        badge::read_badge(e, token_id)
+    }
+
+    pub fn badge_of(e: &Env, holder: Address, kind: Symbol) -> Option<u32> {
+        badge::badge_of(e, &holder, &kind)
+    }
+
+    pub fn has_badge(e: &Env, holder: Address, kind: Symbol) -> bool {
+        badge::badge_of(e, &holder, &kind).is_some()
+    }
+}
+
+#[contractimpl]
+impl NonFungibleToken for SkillBadgeContract {
+    type ContractType = Base;
+
+    fn balance(e: &Env, account: Address) -> u32 {
+        Self::ContractType::balance(e, &account)
+    }
+
+    fn owner_of(e: &Env, token_id: u32) -> Address {
+        Self::ContractType::owner_of(e, token_id)
+    }
+
+    fn transfer(e: &Env, _from: Address, _to: Address, _token_id: u32) {
+        panic_with_error!(e, SkillBadgeError::NonTransferable);
+    }
+
+    fn transfer_from(e: &Env, _spender: Address, _from: Address, _to: Address, _token_id: u32) {
+        panic_with_error!(e, SkillBadgeError::NonTransferable);
+    }
+
+    fn approve(
+        e: &Env,
+        _approver: Address,
+        _approved: Address,
+        _token_id: u32,
+        _live_until_ledger: u32,
+    ) {
+        panic_with_error!(e, SkillBadgeError::NonTransferable);
+    }
+
+    fn approve_for_all(e: &Env, _owner: Address, _operator: Address, _live_until_ledger: u32) {
+        panic_with_error!(e, SkillBadgeError::NonTransferable);
+    }
+
+    fn get_approved(e: &Env, token_id: u32) -> Option<Address> {
+        Self::ContractType::get_approved(e, token_id)
+    }
+
+    fn is_approved_for_all(e: &Env, owner: Address, operator: Address) -> bool {
+        Self::ContractType::is_approved_for_all(e, &owner, &operator)
+    }
+
+    fn name(e: &Env) -> String {
+        Self::ContractType::name(e)
+    }
+
+    fn symbol(e: &Env) -> String {
+        Self::ContractType::symbol(e)
+    }
+
+    fn token_uri(e: &Env, token_id: u32) -> String {
+        Self::ContractType::token_uri(e, token_id)
+    }
+}
+
+#[contractimpl]
+impl AccessControl for SkillBadgeContract {
+    fn has_role(e: &Env, account: Address, role: Symbol) -> Option<u32> {
+        access_control::has_role(e, &account, &role)
+    }
+
+    fn get_role_member_count(e: &Env, role: Symbol) -> u32 {
+        access_control::get_role_member_count(e, &role)
+    }
+
+    fn get_role_member(e: &Env, role: Symbol, index: u32) -> Address {
+        access_control::get_role_member(e, &role, index)
+    }
+
+    fn get_role_admin(e: &Env, role: Symbol) -> Option<Symbol> {
+        access_control::get_role_admin(e, &role)
+    }
+
+    fn get_admin(e: &Env) -> Option<Address> {
+        access_control::get_admin(e)
+    }
+
+    fn grant_role(e: &Env, caller: Address, account: Address, role: Symbol) {
+        access_control::grant_role(e, &caller, &account, &role);
+    }
+
+    fn revoke_role(e: &Env, caller: Address, account: Address, role: Symbol) {
+        access_control::revoke_role(e, &caller, &account, &role);
+    }
+
+    fn renounce_role(e: &Env, caller: Address, role: Symbol) {
+        access_control::renounce_role(e, &caller, &role);
+    }
+
+    fn transfer_admin_role(e: &Env, new_admin: Address, live_until_ledger: u32) {
+        access_control::transfer_admin_role(e, &new_admin, live_until_ledger);
+    }
+
+    fn accept_admin_transfer(e: &Env) {
+        access_control::accept_admin_transfer(e);
+    }
+
+    fn set_role_admin(e: &Env, role: Symbol, admin_role: Symbol) {
+        access_control::set_role_admin(e, &role, &admin_role);
+    }
+
+    fn renounce_admin(e: &Env) {
+        access_control::renounce_admin(e);
+    }
+}
@@ -0,0 +1,7 @@
+#![no_std]
+#![allow(dead_code)]
+
+mod badge;
+pub mod contract;
+#[cfg(test)]
+mod test;
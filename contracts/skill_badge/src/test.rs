@@ -0,0 +1,118 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Symbol};
+
+use crate::contract::{SkillBadgeContract, SkillBadgeContractClient, ISSUER_ROLE};
+
+fn create_client<'a>(e: &Env, owner: &Address) -> SkillBadgeContractClient<'a> {
+    let base_uri = String::from_str(e, "https://badges.example/");
+    let address = e.register(SkillBadgeContract, (owner, base_uri));
+    SkillBadgeContractClient::new(e, &address)
+}
+
+#[test]
+fn issue_mints_badge_to_holder() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+    let kind = Symbol::new(&e, "verified_rust_dev");
+
+    e.mock_all_auths();
+    let token_id = client.issue(&holder, &kind, &owner);
+    assert_eq!(client.owner_of(&token_id), holder);
+    assert_eq!(client.balance(&holder), 1);
+    assert!(client.has_badge(&holder, &kind));
+    assert!(!client.has_badge(&holder, &Symbol::new(&e, "jobs_100")));
+    assert_eq!(client.badge_of(&holder, &kind), Some(token_id));
+
+    let badge = client.get_badge(&token_id);
+    assert_eq!(badge.kind, kind);
+    assert_eq!(badge.issuer, owner);
+    assert_eq!(client.token_uri(&token_id), String::from_str(&e, "https://badges.example/0"));
+}
+
+#[test]
+fn revoke_burns_badge() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+    let kind = Symbol::new(&e, "verified_rust_dev");
+
+    e.mock_all_auths();
+    let token_id = client.issue(&holder, &kind, &owner);
+    client.revoke(&token_id, &owner);
+    assert_eq!(client.balance(&holder), 0);
+    assert!(!client.has_badge(&holder, &kind));
+
+    // A revoked badge can be issued again.
+    client.issue(&holder, &kind, &owner);
+    assert!(client.has_badge(&holder, &kind));
+}
+
+#[test]
+fn granted_issuer_can_issue() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let issuer = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+
+    e.mock_all_auths();
+    client.grant_role(&owner, &issuer, &ISSUER_ROLE);
+    let token_id = client.issue(&holder, &Symbol::new(&e, "jobs_100"), &issuer);
+    assert_eq!(client.get_badge(&token_id).issuer, issuer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn issue_fails_without_issuer_role() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+
+    e.mock_all_auths();
+    client.issue(&holder, &Symbol::new(&e, "jobs_100"), &holder);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn issue_fails_for_badge_already_held() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+    let kind = Symbol::new(&e, "jobs_100");
+
+    e.mock_all_auths();
+    client.issue(&holder, &kind, &owner);
+    client.issue(&holder, &kind, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn transfer_fails_for_badge() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+
+    e.mock_all_auths();
+    let token_id = client.issue(&holder, &Symbol::new(&e, "jobs_100"), &owner);
+    client.transfer(&holder, &Address::generate(&e), &token_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn approve_fails_for_badge() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let client = create_client(&e, &owner);
+
+    e.mock_all_auths();
+    let token_id = client.issue(&holder, &Symbol::new(&e, "jobs_100"), &owner);
+    client.approve(&holder, &Address::generate(&e), &token_id, &1000);
+}